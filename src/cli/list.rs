@@ -1,9 +1,12 @@
 use crate::cli::Execute;
 use clap::Args;
-use jvm_utils::install::{JavaInstall, JavaVersion, Vendor};
+use jvm_utils::install::{Architecture, JavaInstall, JavaVersion, Vendor};
+use jvm_utils::locator::directory::DirectoryJavaLocator;
+use jvm_utils::locator::selection::{JavaRequirement, SelectionPolicy};
 use jvm_utils::locator::LocatorBuilder;
 use serde::{Deserialize, Serialize};
 use std::io;
+use std::ops::Bound;
 use std::path::PathBuf;
 
 #[derive(Args)]
@@ -16,11 +19,22 @@ pub(crate) struct ListCommand {
     #[clap(long, requires = "json")]
     pretty: bool,
 
-    // TODO, we should sort to latest version?
-    /// Only return the first result
+    /// Only return the best match, see --explain
     #[clap(short, long)]
     first: bool,
 
+    /// Prefer JVM's from a known vendor when ranking, may be repeated in order of preference
+    #[clap(long, value_name = "VENDOR")]
+    prefer_vendor: Vec<Vendor>,
+
+    /// Rank the JVM's found, best match first, explaining each rank on stderr
+    #[clap(long)]
+    explain: bool,
+
+    /// Report every candidate examined and why it was rejected on stderr, set by --verbose
+    #[clap(skip)]
+    pub(crate) verbose: bool,
+
     /// Only return the paths to the java executable
     #[clap(short, long)]
     path: bool,
@@ -29,6 +43,10 @@ pub(crate) struct ListCommand {
     #[clap(long, requires = "path")]
     without_javaw: bool,
 
+    /// Don't search JAVA_HOME, JDK_HOME or the PATH
+    #[clap(long)]
+    without_environment: bool,
+
     /// Don't search known system paths
     #[clap(long)]
     without_system: bool,
@@ -41,6 +59,66 @@ pub(crate) struct ListCommand {
     #[clap(long)]
     without_gradle: bool,
 
+    /// Don't search SDKMAN! candidates
+    #[clap(long)]
+    without_sdkman: bool,
+
+    /// Don't search asdf installs
+    #[clap(long)]
+    without_asdf: bool,
+
+    /// Don't search mise installs
+    #[clap(long)]
+    without_mise: bool,
+
+    /// Don't search jenv versions
+    #[clap(long)]
+    without_jenv: bool,
+
+    /// Don't search jabba installs
+    #[clap(long)]
+    without_jabba: bool,
+
+    /// Don't search JVM's installed by Coursier
+    #[clap(long)]
+    without_coursier: bool,
+
+    /// Don't search Nix profiles and GC roots
+    #[clap(long)]
+    without_nix: bool,
+
+    /// Don't search Homebrew and Linuxbrew kegs
+    #[clap(long)]
+    without_homebrew: bool,
+
+    /// Don't search for runtimes bundled with JetBrains IDE's
+    #[clap(long)]
+    without_jetbrains_runtime: bool,
+
+    /// Don't search for runtimes bundled with Eclipse or VS Code
+    #[clap(long)]
+    without_editor: bool,
+
+    /// Don't search for runtimes downloaded by Minecraft launchers
+    #[clap(long)]
+    without_minecraft: bool,
+
+    /// Don't search for JVM's on the Windows host when running within WSL
+    #[clap(long)]
+    without_wsl: bool,
+
+    /// Don't search for JDK's declared in Maven toolchains.xml files
+    #[clap(long)]
+    without_maven_toolchains: bool,
+
+    /// Also search the given directory, may be repeated
+    #[clap(long, value_name = "DIR")]
+    search_dir: Vec<PathBuf>,
+
+    /// How deep to search directories given with --search-dir or JVM_UTILS_PATHS
+    #[clap(long, value_name = "DEPTH")]
+    search_depth: Option<usize>,
+
     /// Don't return any OpenJ9 JVM's
     #[clap(long)]
     ignore_openj9: bool,
@@ -53,14 +131,49 @@ pub(crate) struct ListCommand {
     #[clap(long)]
     filter: Option<JavaVersion>,
 
-    /// Only find JVM's from a known vendor
+    /// Only find JVM's of at least the specified version
+    #[clap(long, value_name = "VERSION")]
+    min_version: Option<JavaVersion>,
+
+    /// Only find JVM's of at most the specified version
+    #[clap(long, value_name = "VERSION")]
+    max_version: Option<JavaVersion>,
+
+    /// Only find JVM's from a known vendor, may be repeated
+    #[clap(long)]
+    vendor_filter: Vec<Vendor>,
+
+    /// Only find JVM's for the specified architecture, may be repeated
+    #[clap(long, value_name = "ARCH")]
+    arch: Vec<Architecture>,
+
+    /// Don't return JVM's under the given directory, may be repeated
+    #[clap(long, value_name = "DIR")]
+    exclude_path: Vec<PathBuf>,
+
+    /// Read JVM properties from their release file where possible, instead of running them
     #[clap(long)]
-    vendor_filter: Option<Vendor>,
+    prefer_release_file: bool,
+
+    /// Cache probed JVM properties between runs
+    #[clap(long)]
+    probe_cache: bool,
+
+    /// Probe all JVM's again, replacing any cached properties, requires --probe-cache
+    #[clap(long, requires = "probe_cache")]
+    refresh_probe_cache: bool,
+
+    /// Search the system in the given directory instead, such as an extracted container image. Nothing is executed
+    #[clap(long, value_name = "DIR")]
+    sysroot: Option<PathBuf>,
 }
 
 impl Execute for ListCommand {
     fn execute(self) -> io::Result<()> {
         let mut locator = LocatorBuilder::new();
+        if !self.without_environment {
+            locator.with_environment_locator();
+        }
         if !self.without_system {
             locator.with_platform_locator();
         }
@@ -70,6 +183,54 @@ impl Execute for ListCommand {
         if !self.without_gradle {
             locator.with_gradle_locator();
         }
+        if !self.without_sdkman {
+            locator.with_sdkman_locator();
+        }
+        if !self.without_asdf {
+            locator.with_asdf_locator();
+        }
+        if !self.without_mise {
+            locator.with_mise_locator();
+        }
+        if !self.without_jenv {
+            locator.with_jenv_locator();
+        }
+        if !self.without_jabba {
+            locator.with_jabba_locator();
+        }
+        if !self.without_coursier {
+            locator.with_coursier_locator();
+        }
+        if !self.without_nix {
+            locator.with_nix_locator();
+        }
+        if !self.without_homebrew {
+            locator.with_homebrew_locator();
+        }
+        if !self.without_jetbrains_runtime {
+            locator.with_jetbrains_runtime_locator();
+        }
+        if !self.without_editor {
+            locator.with_editor_locator();
+        }
+        if !self.without_minecraft {
+            locator.with_minecraft_locator();
+        }
+        if !self.without_maven_toolchains {
+            locator.with_maven_toolchains_locator();
+        }
+        if !self.without_wsl {
+            locator.with_wsl_locator();
+        }
+
+        let mut directory = DirectoryJavaLocator::from_env();
+        for dir in &self.search_dir {
+            directory.with_root(dir);
+        }
+        if let Some(depth) = self.search_depth {
+            directory.max_depth(depth);
+        }
+        locator.with_locator(Box::new(directory));
 
         if self.ignore_openj9 {
             locator.ignore_openj9();
@@ -83,13 +244,65 @@ impl Execute for ListCommand {
             locator.filter(version);
         }
 
-        if let Some(vendor) = &self.vendor_filter {
-            locator.vendor_filter(vendor);
+        if self.min_version.is_some() || self.max_version.is_some() {
+            let min = self.min_version.map_or(Bound::Unbounded, Bound::Included);
+            let max = self.max_version.map_or(Bound::Unbounded, Bound::Included);
+            locator.version_range((min, max));
+        }
+
+        if !self.vendor_filter.is_empty() {
+            locator.vendors(self.vendor_filter.iter().copied());
+        }
+
+        if !self.arch.is_empty() {
+            locator.architectures(self.arch.iter().copied());
+        }
+
+        for dir in &self.exclude_path {
+            locator.exclude_path(dir);
+        }
+
+        if self.prefer_release_file {
+            locator.prefer_release_file();
+        }
+
+        if self.probe_cache {
+            locator.use_probe_cache();
+        }
+
+        if self.refresh_probe_cache {
+            locator.refresh_probe_cache();
+        }
+
+        if let Some(sysroot) = &self.sysroot {
+            locator.sysroot(sysroot);
         }
 
-        let mut located = locator.locate();
-        if self.first && !located.is_empty() {
-            located = vec![located.remove(0)];
+        let (mut located, diagnostics) = locator.locate_with_diagnostics();
+        if self.verbose {
+            for x in &diagnostics.candidates {
+                match &x.rejection {
+                    Some(reason) => eprintln!("Rejected {:?} found by {}: {}", x.path, x.located_by.join(", "), reason),
+                    None => eprintln!("Located {:?} found by {}", x.path, x.located_by.join(", ")),
+                }
+            }
+            eprintln!("Examined {} candidates, rejected {}.", diagnostics.candidates.len(), diagnostics.rejected().count());
+        }
+        if self.first || self.explain {
+            let mut policy = SelectionPolicy::new();
+            for vendor in &self.prefer_vendor {
+                policy.prefer_vendor(*vendor);
+            }
+            let ranked = policy.rank(&JavaRequirement::any(), located);
+            if self.explain {
+                for x in &ranked {
+                    eprintln!("#{} {:?}: {}", x.rank, x.install.java_home, x.reasons.join(", "));
+                }
+            }
+            located = ranked.into_iter().map(|e| e.install).collect();
+        }
+        if self.first {
+            located.truncate(1);
         }
 
         if self.path {
@@ -110,7 +323,11 @@ impl ListCommand {
             }
         } else {
             for x in located {
-                println!("Found java version {:?} at {:?}", x.lang_version, x.java_home)
+                if x.located_by.is_empty() {
+                    println!("Found java version {:?} at {:?}", x.lang_version, x.java_home)
+                } else {
+                    println!("Found java version {:?} at {:?} by {}", x.lang_version, x.java_home, x.located_by.join(", "))
+                }
             }
         }
 
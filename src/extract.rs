@@ -37,3 +37,23 @@ pub fn extract_java_properties(java_executable: impl AsRef<Path>, props: impl In
 
     Some(properties)
 }
+
+/// Reads the properties from the `release` file of a Java installation.
+///
+/// Most modern JVM's ship this file in their home directory, it contains simple
+/// `KEY="value"` pairs describing the installation, such as `JAVA_VERSION` and `OS_ARCH`.
+///
+/// * `java_home` - The home directory of the Java installation.
+///
+/// # Returns
+/// Some containing the properties, otherwise None if the file could not be read.
+pub fn read_release_properties(java_home: impl AsRef<Path>) -> Option<HashMap<String, String>> {
+    let contents = fs::read_to_string(java_home.as_ref().join("release")).ok()?;
+
+    let properties = contents.lines()
+        .filter_map(|e| e.split_once('='))
+        .map(|(k, v)| (k.trim().to_string(), v.trim().trim_matches('"').to_string()))
+        .filter(|(k, _)| !k.is_empty() && !k.starts_with('#'))
+        .collect();
+    Some(properties)
+}
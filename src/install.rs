@@ -1,15 +1,17 @@
-use crate::extract::extract_java_properties;
+use crate::extract::{extract_java_properties, read_release_properties};
 #[cfg(feature = "cli")]
 use clap::ValueEnum;
 use num_enum::TryFromPrimitive;
 use regex::Regex;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
 use std::path::{Path, PathBuf};
 
 /// Represents a limited set of current and future java versions.
 #[repr(usize)]
-#[derive(Debug, PartialEq, Eq, Copy, Clone, TryFromPrimitive)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, TryFromPrimitive)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "cli", derive(ValueEnum))]
 pub enum JavaVersion {
@@ -76,7 +78,7 @@ impl JavaVersion {
         if v_split.len() > 1 && v_split[0] == 1 {
             return Self::try_from(v_split[1]).ok();
         }
-        Self::try_from(v_split[0]).ok()
+        Self::try_from(*v_split.first()?).ok()
     }
 
     pub fn ordinal(self) -> usize {
@@ -100,6 +102,7 @@ impl JavaVersion {
 #[repr(usize)]
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
 pub enum Architecture {
     X86 = 1,
     X86_64 = 2,
@@ -203,6 +206,47 @@ impl Vendor {
     }
 }
 
+/// Where the properties of a [`JavaInstall`] were read from.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PropertySource {
+    /// The properties were extracted by running the installation's java executable.
+    Probe,
+    /// The properties were read from the installation's `release` file, without
+    /// running anything.
+    ReleaseFile,
+}
+
+/// Why the properties of a Java installation couldn't be read.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ProbeError {
+    /// There is no java executable for this platform, and no `release` file.
+    MissingExecutable,
+    /// There is no `release` file to read the properties from.
+    MissingReleaseFile,
+    /// The java executable couldn't be run, or didn't report every property.
+    ProbeFailed,
+    /// The reported version isn't a known [`JavaVersion`].
+    UnparseableVersion(String),
+    /// The reported architecture isn't a known [`Architecture`].
+    UnknownArchitecture(String),
+    /// The installation is incomplete, such as a partial download, and is missing this file.
+    MissingFile(PathBuf),
+}
+
+impl fmt::Display for ProbeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProbeError::MissingExecutable => write!(f, "no java executable or release file"),
+            ProbeError::MissingReleaseFile => write!(f, "no release file"),
+            ProbeError::ProbeFailed => write!(f, "failed to probe java executable"),
+            ProbeError::UnparseableVersion(version) => write!(f, "unparseable version {:?}", version),
+            ProbeError::UnknownArchitecture(arch) => write!(f, "unknown architecture {:?}", arch),
+            ProbeError::MissingFile(path) => write!(f, "incomplete installation, missing {:?}", path),
+        }
+    }
+}
+
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
 pub struct JavaInstall {
@@ -218,6 +262,16 @@ pub struct JavaInstall {
 
     pub is_openj9: bool,
     pub is_jdk: bool,
+
+    pub property_source: PropertySource,
+    /// Extra information provided by the locator which found this installation.
+    pub metadata: BTreeMap<String, String>,
+    /// Other home directories this installation was found at, such as symlinks to its
+    /// home. These are always directories, never java executables.
+    pub aliases: Vec<PathBuf>,
+    /// The names of the locators which found this installation, see `JavaLocator::name`.
+    /// The first is the [`JavaInstall::source`] of the installation.
+    pub located_by: Vec<String>,
 }
 
 impl JavaInstall {
@@ -242,9 +296,22 @@ impl JavaInstall {
             architecture,
             is_openj9: impl_name.contains("j9"),
             is_jdk: Self::get_executable(install_dir, "javac").exists(),
+            property_source: PropertySource::Probe,
+            metadata: BTreeMap::new(),
+            aliases: Vec::new(),
+            located_by: Vec::new(),
         })
     }
 
+    /// Gets the name of the locator which first found this installation, such as
+    /// `Gradle`. See [`JavaInstall::located_by`] for every locator which found it.
+    ///
+    /// # Returns
+    /// Some containing the locator name, otherwise None if it wasn't found by a locator.
+    pub fn source(&self) -> Option<&str> {
+        self.located_by.first().map(String::as_str)
+    }
+
     /// Retrieves the bin directory for a given installation directory.
     ///
     /// This method transparently resolves any platform specific offsets from the
@@ -312,6 +379,19 @@ impl JavaInstall {
         home_dir.as_ref().join("bin").join(executable).with_extension(exe_suffix())
     }
 
+    /// The system properties extracted from a java executable in order to parse a [`JavaInstall`].
+    pub const PROPERTIES: [&str; 9] = [
+        "java.home",
+        "java.version",
+        "java.vendor",
+        "os.arch",
+        "java.vm.name",
+        "java.vm.version",
+        "java.runtime.name",
+        "java.runtime.version",
+        "java.class.version",
+    ];
+
     /// Parse a Java Installation's properties and attributes from the given executable.
     ///
     /// The executable is not required to exist.
@@ -321,37 +401,101 @@ impl JavaInstall {
     /// # Returns
     /// Maybe a JavaInstall with extracted properties and attributes.
     pub fn parse(executable: impl AsRef<Path>) -> Option<Self> {
-        static PROPERTIES: [&'static str; 9] = [
-            "java.home",
-            "java.version",
-            "java.vendor",
-            "os.arch",
-            "java.vm.name",
-            "java.vm.version",
-            "java.runtime.name",
-            "java.runtime.version",
-            "java.class.version",
-        ];
-        let props = extract_java_properties(executable, PROPERTIES)?;
-
-        let java_home = props.get("java.home")
-            .map(Path::new)?;
-
-        let java_home_real: &Path;
-        if java_home.file_name()?.eq("jre") && java_home.parent()?.join("bin").exists() {
-            java_home_real = java_home.parent()?;
-        } else {
-            java_home_real = java_home;
+        Self::try_parse(executable).ok()
+    }
+
+    /// Parse a Java Installation's properties and attributes from the given executable.
+    ///
+    /// * `executable` - The executable path.
+    ///
+    /// # Returns
+    /// The JavaInstall, otherwise why it couldn't be parsed.
+    pub fn try_parse(executable: impl AsRef<Path>) -> Result<Self, ProbeError> {
+        if !executable.as_ref().exists() {
+            return Err(ProbeError::MissingExecutable);
         }
+        let props = extract_java_properties(executable, Self::PROPERTIES).ok_or(ProbeError::ProbeFailed)?;
+        Self::try_from_properties(&props)
+    }
+
+    /// Parse a Java Installation's properties and attributes from previously
+    /// extracted system properties.
+    ///
+    /// * `props` - The extracted properties, see [`JavaInstall::PROPERTIES`].
+    ///
+    /// # Returns
+    /// Maybe a JavaInstall with the given properties and attributes.
+    pub fn from_properties(props: &HashMap<String, String>) -> Option<Self> {
+        Self::try_from_properties(props).ok()
+    }
+
+    /// Parse a Java Installation's properties and attributes from previously
+    /// extracted system properties.
+    ///
+    /// * `props` - The extracted properties, see [`JavaInstall::PROPERTIES`].
+    ///
+    /// # Returns
+    /// The JavaInstall, otherwise why it couldn't be parsed.
+    pub fn try_from_properties(props: &HashMap<String, String>) -> Result<Self, ProbeError> {
+        let prop = |key: &str| props.get(key).ok_or(ProbeError::ProbeFailed);
+        let java_home = Path::new(prop("java.home")?);
+
+        let java_home_real = match java_home.parent() {
+            Some(parent) if java_home.ends_with("jre") && parent.join("bin").exists() => parent,
+            _ => java_home,
+        };
 
+        let version = prop("java.version")?;
+        let arch = prop("os.arch")?;
         Self::new(
             java_home_real,
-            props.get("java.vendor")?.into(),
-            props.get("java.vm.name")?.into(),
-            props.get("java.version")?.into(),
-            props.get("java.runtime.name")?.into(),
-            props.get("java.runtime.version")?.into(),
-            Architecture::parse(props.get("os.arch")?)?,
-        )
+            prop("java.vendor")?.into(),
+            prop("java.vm.name")?.into(),
+            version.into(),
+            prop("java.runtime.name")?.into(),
+            prop("java.runtime.version")?.into(),
+            Architecture::parse(arch).ok_or_else(|| ProbeError::UnknownArchitecture(arch.into()))?,
+        ).ok_or_else(|| ProbeError::UnparseableVersion(version.into()))
+    }
+
+    /// Parse a Java Installation's properties and attributes from its `release` file.
+    ///
+    /// Nothing is executed, making this suitable for installations which can't be run
+    /// on this system, such as those for a foreign architecture or operating system.
+    /// As the `release` file does not contain every property, `impl_name` is the JVM
+    /// variant (such as `Hotspot`) and `runtime_name` is empty.
+    ///
+    /// * `java_home` - The home directory of the Java installation.
+    ///
+    /// # Returns
+    /// Maybe a JavaInstall with the properties and attributes from the `release` file.
+    pub fn from_release_file(java_home: impl AsRef<Path>) -> Option<Self> {
+        Self::try_from_release_file(java_home).ok()
+    }
+
+    /// Parse a Java Installation's properties and attributes from its `release` file.
+    ///
+    /// * `java_home` - The home directory of the Java installation.
+    ///
+    /// # Returns
+    /// The JavaInstall, otherwise why it couldn't be parsed.
+    pub fn try_from_release_file(java_home: impl AsRef<Path>) -> Result<Self, ProbeError> {
+        let props = read_release_properties(&java_home).ok_or(ProbeError::MissingReleaseFile)?;
+        let version = props.get("JAVA_VERSION").ok_or_else(|| ProbeError::UnparseableVersion(String::new()))?;
+        let arch = props.get("OS_ARCH").map(String::as_str).unwrap_or_default();
+
+        let mut install = Self::new(
+            &java_home,
+            props.get("IMPLEMENTOR").cloned().unwrap_or_default(),
+            props.get("JVM_VARIANT").cloned().unwrap_or_default(),
+            version.clone(),
+            String::new(),
+            props.get("JAVA_RUNTIME_VERSION").unwrap_or(version).clone(),
+            Architecture::parse(arch).ok_or_else(|| ProbeError::UnknownArchitecture(arch.into()))?,
+        ).ok_or_else(|| ProbeError::UnparseableVersion(version.clone()))?;
+        install.property_source = PropertySource::ReleaseFile;
+        // The compiler may be for another platform, check the listed modules too.
+        install.is_jdk |= props.get("MODULES").is_some_and(|e| e.split_whitespace().any(|m| m == "jdk.compiler"));
+        Ok(install)
     }
 }
@@ -1,23 +1,77 @@
+pub mod asdf;
+#[cfg(feature = "serde_json")]
+pub mod cache;
+pub mod coursier;
+pub mod diagnostics;
+pub mod directory;
+pub mod editor;
+pub mod environment;
 pub mod gradle;
+pub mod homebrew;
 pub mod intellij;
+pub mod jbr;
+pub mod jabba;
+pub mod jenv;
+pub mod minecraft;
+pub mod maven;
+pub mod mise;
+pub mod nix;
 pub mod platform;
+pub mod sdkman;
+pub mod selection;
+pub mod sysroot;
+pub mod wsl;
 
-use crate::install::{JavaInstall, JavaVersion, Vendor};
+use crate::install::{Architecture, JavaInstall, JavaVersion, ProbeError, Vendor};
+#[cfg(feature = "serde_json")]
+use crate::locator::cache::ProbeCache;
+use crate::locator::asdf::AsdfJavaLocator;
+use crate::locator::coursier::CoursierJavaLocator;
+use crate::locator::diagnostics::{CandidateDiagnostic, Diagnostics, RejectionReason};
+use crate::locator::directory::DirectoryJavaLocator;
+use crate::locator::editor::EditorJavaLocator;
+use crate::locator::environment::EnvironmentJavaLocator;
 use crate::locator::gradle::GradleJavaLocator;
+use crate::locator::homebrew::HomebrewJavaLocator;
 use crate::locator::intellij::IntelliJJavaLocator;
+use crate::locator::jbr::JetBrainsRuntimeJavaLocator;
+use crate::locator::jabba::JabbaJavaLocator;
+use crate::locator::jenv::JenvJavaLocator;
+use crate::locator::maven::MavenToolchainsJavaLocator;
+use crate::locator::minecraft::MinecraftJavaLocator;
+use crate::locator::mise::MiseJavaLocator;
+use crate::locator::nix::NixJavaLocator;
 use crate::locator::platform::PlatformJavaLocator;
+use crate::locator::sdkman::SdkmanJavaLocator;
+use crate::locator::selection::{JavaRequirement, RankedInstall, SelectionPolicy};
+use crate::locator::sysroot::{Sysroot, SYSROOT_PATH};
+use crate::locator::wsl::WslJavaLocator;
 use crate::log_debug;
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::Path;
+use std::mem;
+use std::num::NonZeroUsize;
+use std::ops::{Bound, RangeBounds};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// A predicate an installation must match to be located.
+type InstallFilter = Box<dyn Fn(&JavaInstall) -> bool>;
 
 /// A modular Java locator.
 #[derive(Default)]
 pub struct LocatorBuilder {
     use_javaw: bool,
-    ignore_openj9: bool,
-    jdk_only: bool,
-    filter: Option<JavaVersion>,
-    vendor_filter: Option<Vendor>,
+    filters: Vec<(String, InstallFilter)>,
+    max_parallel_probes: Option<NonZeroUsize>,
+    prefer_release_file: bool,
+    sysroot: Sysroot,
+    #[cfg(feature = "serde_json")]
+    probe_cache: Option<PathBuf>,
+    #[cfg(feature = "serde_json")]
+    refresh_probe_cache: bool,
     children: Vec<Box<dyn JavaLocator>>,
 }
 
@@ -32,25 +86,146 @@ impl LocatorBuilder {
     }
 
     pub fn ignore_openj9(&mut self) -> &mut Self {
-        self.ignore_openj9 = true;
-        self
+        self.add_filter("ignore OpenJ9", |e| !e.is_openj9)
     }
 
     pub fn jdk_only(&mut self) -> &mut Self {
-        self.jdk_only = true;
-        self
+        self.add_filter("JDK only", |e| e.is_jdk)
     }
 
     pub fn filter(&mut self, version: &JavaVersion) -> &mut Self {
-        self.filter = Some(version.clone());
-        self
+        self.version_range(*version..=*version)
     }
 
     pub fn vendor_filter(&mut self, vendor: &Vendor) -> &mut Self {
-        self.vendor_filter = Some(vendor.clone());
+        self.vendors([*vendor])
+    }
+
+    /// Only locate installations matching the given predicate.
+    ///
+    /// Filters are combined, an installation must match every filter to be located.
+    ///
+    /// * `predicate` - Returns true for installations to keep.
+    pub fn filter_by(&mut self, predicate: impl Fn(&JavaInstall) -> bool + 'static) -> &mut Self {
+        self.add_filter("custom filter", predicate)
+    }
+
+    /// Only locate installations with a language version in the given range.
+    ///
+    /// * `range` - The versions to keep, such as `JavaVersion::Java11..` for 11 or newer.
+    pub fn version_range(&mut self, range: impl RangeBounds<JavaVersion>) -> &mut Self {
+        let range = (range.start_bound().cloned(), range.end_bound().cloned());
+        let bound = |bound: Bound<&JavaVersion>| match bound {
+            Bound::Included(e) | Bound::Excluded(e) => e.short_string(),
+            Bound::Unbounded => String::new(),
+        };
+        let separator = if matches!(range.1, Bound::Included(_)) { "..=" } else { ".." };
+        let description = format!("version {}{}{}", bound(range.0.as_ref()), separator, bound(range.1.as_ref()));
+        self.add_filter(description, move |e| range.contains(&e.lang_version))
+    }
+
+    /// Only locate installations from one of the given known vendors.
+    ///
+    /// * `vendors` - The vendors to keep, such as Temurin and Zulu.
+    pub fn vendors(&mut self, vendors: impl IntoIterator<Item = Vendor>) -> &mut Self {
+        let vendors: Vec<Vendor> = vendors.into_iter().collect();
+        self.add_filter(format!("vendor {:?}", &vendors), move |e| e.known_vendor.is_some_and(|v| vendors.contains(&v)))
+    }
+
+    /// Only locate installations for one of the given architectures.
+    ///
+    /// * `architectures` - The architectures to keep.
+    pub fn architectures(&mut self, architectures: impl IntoIterator<Item = Architecture>) -> &mut Self {
+        let architectures: Vec<Architecture> = architectures.into_iter().collect();
+        self.add_filter(format!("architecture {:?}", &architectures), move |e| architectures.contains(&e.architecture))
+    }
+
+    /// Don't locate installations under the given directory.
+    ///
+    /// An installation is excluded if its home, or another home directory it was found
+    /// at in [`JavaInstall::aliases`], is under the directory. Links to its java
+    /// executable elsewhere, such as `/usr/bin/java`, don't exclude it. Within a
+    /// sysroot, this is a path on the host.
+    ///
+    /// * `dir` - The directory to exclude, such as `/tmp`.
+    pub fn exclude_path(&mut self, dir: impl AsRef<Path>) -> &mut Self {
+        let dir = dir.as_ref().to_path_buf();
+        self.add_filter(format!("excluded path {:?}", &dir), move |e| !e.java_home.starts_with(&dir) && !e.aliases.iter().any(|a| a.starts_with(&dir)))
+    }
+
+    fn add_filter(&mut self, description: impl Into<String>, predicate: impl Fn(&JavaInstall) -> bool + 'static) -> &mut Self {
+        self.filters.push((description.into(), Box::new(predicate)));
+        self
+    }
+
+    /// Limit the number of Java installations which may be probed at the same time.
+    ///
+    /// Defaults to the available parallelism of the system.
+    ///
+    /// * `limit` - The maximum number of concurrent probes.
+    pub fn max_parallel_probes(&mut self, limit: NonZeroUsize) -> &mut Self {
+        self.max_parallel_probes = Some(limit);
+        self
+    }
+
+    /// Read the properties of each installation from its `release` file where possible,
+    /// instead of running its java executable.
+    ///
+    /// Installations without a usable `release` file are still probed.
+    pub fn prefer_release_file(&mut self) -> &mut Self {
+        self.prefer_release_file = true;
+        self
+    }
+
+    /// Search the system contained in the given directory instead of the host, such
+    /// as an extracted container image or a chroot. See [`Sysroot`].
+    ///
+    /// Nothing within the sysroot is executed, as it may be for another architecture
+    /// or libc. Installations are read from their `release` file, those without one
+    /// are skipped. The path of each installation within the sysroot is recorded as
+    /// [`SYSROOT_PATH`] metadata.
+    ///
+    /// * `root` - The directory to treat as `/`.
+    pub fn sysroot(&mut self, root: impl AsRef<Path>) -> &mut Self {
+        self.sysroot = Sysroot::new(root);
+        self
+    }
+
+    /// Cache the properties probed from each java executable on disk, in the
+    /// default location. See [`ProbeCache::default_path`].
+    ///
+    /// Cached properties are reused for as long as the executable's path, size and
+    /// modification time remain unchanged.
+    #[cfg(feature = "serde_json")]
+    pub fn use_probe_cache(&mut self) -> &mut Self {
+        self.probe_cache = ProbeCache::default_path();
+        self
+    }
+
+    /// Cache the properties probed from each java executable on disk, in the given file.
+    ///
+    /// * `path` - The path of the cache file.
+    #[cfg(feature = "serde_json")]
+    pub fn probe_cache_path(&mut self, path: impl AsRef<Path>) -> &mut Self {
+        self.probe_cache = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Ignore any existing probe cache entries, probing every java executable again.
+    ///
+    /// The entries of the executables probed are replaced with the fresh results,
+    /// entries for other executables are kept. Has no effect unless a probe cache
+    /// is in use.
+    #[cfg(feature = "serde_json")]
+    pub fn refresh_probe_cache(&mut self) -> &mut Self {
+        self.refresh_probe_cache = true;
         self
     }
 
+    pub fn with_environment_locator(&mut self) -> &mut Self {
+        self.with_locator(Box::new(EnvironmentJavaLocator::new()))
+    }
+
     pub fn with_platform_locator(&mut self) -> &mut Self {
         self.with_locator(Box::new(PlatformJavaLocator::new()))
     }
@@ -63,80 +238,573 @@ impl LocatorBuilder {
         self.with_locator(Box::new(IntelliJJavaLocator::new()))
     }
 
+    pub fn with_sdkman_locator(&mut self) -> &mut Self {
+        self.with_locator(Box::new(SdkmanJavaLocator::new()))
+    }
+
+    pub fn with_asdf_locator(&mut self) -> &mut Self {
+        self.with_locator(Box::new(AsdfJavaLocator::new()))
+    }
+
+    pub fn with_mise_locator(&mut self) -> &mut Self {
+        self.with_locator(Box::new(MiseJavaLocator::new()))
+    }
+
+    pub fn with_jenv_locator(&mut self) -> &mut Self {
+        self.with_locator(Box::new(JenvJavaLocator::new()))
+    }
+
+    pub fn with_jabba_locator(&mut self) -> &mut Self {
+        self.with_locator(Box::new(JabbaJavaLocator::new()))
+    }
+
+    pub fn with_coursier_locator(&mut self) -> &mut Self {
+        self.with_locator(Box::new(CoursierJavaLocator::new()))
+    }
+
+    pub fn with_nix_locator(&mut self) -> &mut Self {
+        self.with_locator(Box::new(NixJavaLocator::new()))
+    }
+
+    pub fn with_homebrew_locator(&mut self) -> &mut Self {
+        self.with_locator(Box::new(HomebrewJavaLocator::new()))
+    }
+
+    pub fn with_jetbrains_runtime_locator(&mut self) -> &mut Self {
+        self.with_locator(Box::new(JetBrainsRuntimeJavaLocator::new()))
+    }
+
+    pub fn with_editor_locator(&mut self) -> &mut Self {
+        self.with_locator(Box::new(EditorJavaLocator::new()))
+    }
+
+    pub fn with_minecraft_locator(&mut self) -> &mut Self {
+        self.with_locator(Box::new(MinecraftJavaLocator::new()))
+    }
+
+    pub fn with_maven_toolchains_locator(&mut self) -> &mut Self {
+        self.with_locator(Box::new(MavenToolchainsJavaLocator::new()))
+    }
+
+    pub fn with_wsl_locator(&mut self) -> &mut Self {
+        self.with_locator(Box::new(WslJavaLocator::new()))
+    }
+
+    /// Search the directories listed in the `JVM_UTILS_PATHS` environment variable.
+    /// See [`DirectoryJavaLocator`] for searching other directories.
+    pub fn with_directory_locator(&mut self) -> &mut Self {
+        self.with_locator(Box::new(DirectoryJavaLocator::from_env()))
+    }
+
     pub fn with_locator(&mut self, locator: Box<dyn JavaLocator>) -> &mut Self {
         self.children.push(locator);
         self
     }
 
+    /// Locate all Java installations from the configured locators.
+    ///
+    /// Candidates are collected from every locator in the order they were added, then
+    /// probed in parallel. The result is ordered by candidate discovery order, and contains
+    /// each java home at most once. Homes are canonicalized, so an installation found
+    /// through several symlinks or locators is merged, keeping every path it was found
+    /// at in [`JavaInstall::aliases`] and every locator in [`JavaInstall::located_by`].
+    ///
+    /// # Returns
+    /// The installations found, after filtering.
     pub fn locate(&self) -> Vec<JavaInstall> {
-        let vec: Vec<JavaInstall> = self.children.iter()
-            .filter_map(|e| e.locate())
-            .flatten()
-            .filter(|e| self.filter.is_none() || self.filter.eq(&Some(e.lang_version.clone())))
-            .filter(|e| !self.ignore_openj9 || !e.is_openj9)
-            .filter(|e| !self.jdk_only || e.is_jdk)
-            .filter(|e| self.vendor_filter.is_none() || self.vendor_filter.eq(&e.known_vendor))
-            .collect();
-        vec
+        self.locate_with_diagnostics().0
+    }
+
+    /// Locate all Java installations, reporting why any candidate wasn't located.
+    ///
+    /// See [`LocatorBuilder::locate`].
+    ///
+    /// # Returns
+    /// The installations found, after filtering, and a report of every candidate examined.
+    pub fn locate_with_diagnostics(&self) -> (Vec<JavaInstall>, Diagnostics) {
+        let candidates = self.find_candidates();
+        log_debug!("Probing {} candidates for JVM's.", candidates.len());
+
+        let results = if self.sysroot.is_host() {
+            self.probe_candidates(&candidates)
+        } else {
+            probe_candidates(&candidates, self.probe_limit(), false, JavaCandidate::read_release_file)
+        };
+
+        let mut installs: Vec<JavaInstall> = Vec::new();
+        let mut diagnostics = Diagnostics::default();
+        // The home each candidate was probed as, to find the candidates of filtered installs.
+        let mut homes: Vec<Option<PathBuf>> = Vec::new();
+        for (candidate, result) in candidates.iter().zip(results) {
+            let rejection = match result {
+                Ok(install) => {
+                    homes.push(Some(install.java_home.clone()));
+                    add_install(&mut installs, install);
+                    None
+                }
+                Err(err) => {
+                    log_debug!("Rejected candidate {:?}, {}.", &candidate.path, &err);
+                    homes.push(None);
+                    Some(RejectionReason::Probe(err))
+                }
+            };
+            diagnostics.candidates.push(CandidateDiagnostic {
+                path: candidate.path.clone(),
+                located_by: candidate.located_by.clone(),
+                rejection,
+            });
+        }
+        for install in &installs {
+            self.children.iter().for_each(|e| e.verify(install));
+        }
+
+        let mut located: Vec<JavaInstall> = Vec::new();
+        for install in installs {
+            let Some((description, _)) = self.filters.iter().find(|(_, filter)| !filter(&install)) else {
+                located.push(install);
+                continue;
+            };
+            log_debug!("Filtered install {:?} by {}.", &install.java_home, description);
+            for (diagnostic, home) in diagnostics.candidates.iter_mut().zip(&homes) {
+                if home.as_ref() == Some(&install.java_home) {
+                    diagnostic.rejection = Some(RejectionReason::Filtered(description.clone()));
+                }
+            }
+        }
+        (located, diagnostics)
+    }
+
+    /// Locate all Java installations satisfying a requirement, best match first.
+    ///
+    /// * `requirement` - The requirement installations must satisfy.
+    /// * `policy` - How installations are ranked, see [`SelectionPolicy`].
+    ///
+    /// # Returns
+    /// The ranked installations, each with an explanation of its rank.
+    pub fn select(&self, requirement: &JavaRequirement, policy: &SelectionPolicy) -> Vec<RankedInstall> {
+        policy.rank(requirement, self.locate())
+    }
+
+    fn find_candidates(&self) -> Vec<JavaCandidate> {
+        let mut candidates: Vec<JavaCandidate> = Vec::new();
+        for child in &self.children {
+            for candidate in child.find_candidates(&self.sysroot).into_iter().flatten() {
+                let mut candidate = resolve_candidate(&self.sysroot, child.name(), candidate);
+                if let Some(path) = self.sysroot.root().and_then(|_| self.sysroot.target_path(&candidate.path)) {
+                    candidate.with_metadata(SYSROOT_PATH, path.to_string_lossy());
+                }
+                merge_candidate(&mut candidates, candidate);
+            }
+        }
+        candidates
+    }
+
+    #[cfg(feature = "serde_json")]
+    fn probe_candidates(&self, candidates: &[JavaCandidate]) -> Vec<Result<JavaInstall, ProbeError>> {
+        let Some(path) = &self.probe_cache else {
+            return probe_candidates(candidates, self.probe_limit(), self.prefer_release_file, JavaCandidate::probe);
+        };
+
+        let cache = if self.refresh_probe_cache {
+            ProbeCache::load(path).refreshing()
+        } else {
+            ProbeCache::load(path)
+        };
+        let installs = probe_candidates(candidates, self.probe_limit(), self.prefer_release_file, |e| e.probe_cached(&cache));
+        if let Err(err) = cache.save() {
+            log_warn!("Failed to save probe cache {:?}. {}", path, err);
+        }
+        installs
+    }
+
+    #[cfg(not(feature = "serde_json"))]
+    fn probe_candidates(&self, candidates: &[JavaCandidate]) -> Vec<Result<JavaInstall, ProbeError>> {
+        probe_candidates(candidates, self.probe_limit(), self.prefer_release_file, JavaCandidate::probe)
+    }
+
+    fn probe_limit(&self) -> usize {
+        self.max_parallel_probes
+            .or_else(|| thread::available_parallelism().ok())
+            .map_or(1, NonZeroUsize::get)
+    }
+}
+
+/// A directory which looks like it contains a Java installation.
+///
+/// Candidates are cheap to find, they have not yet been probed. Directories a locator
+/// expected to contain an installation, but which don't, are kept as rejected
+/// candidates so they are reported in [`Diagnostics`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JavaCandidate {
+    pub path: PathBuf,
+    /// Extra information known by the locator, copied to the probed [`JavaInstall`].
+    pub metadata: BTreeMap<String, String>,
+    /// Other home directories the candidate was found at, such as symlinks to its home.
+    pub aliases: Vec<PathBuf>,
+    /// The names of the locators which found the candidate.
+    pub located_by: Vec<String>,
+    /// Why the candidate was rejected before probing, such candidates are never probed.
+    pub rejection: Option<ProbeError>,
+}
+
+impl JavaCandidate {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            metadata: BTreeMap::new(),
+            aliases: Vec::new(),
+            located_by: Vec::new(),
+            rejection: None,
+        }
+    }
+
+    /// Attach extra information to the candidate.
+    ///
+    /// * `key` - The metadata key, such as `sdkman.identifier`.
+    /// * `value` - The metadata value.
+    ///
+    /// # Returns
+    /// The same candidate, for chaining.
+    pub fn with_metadata(&mut self, key: impl Into<String>, value: impl Into<String>) -> &mut Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// Record another path the candidate was found at.
+    ///
+    /// * `path` - The alias, ignored if it is the candidate's own path.
+    ///
+    /// # Returns
+    /// The same candidate, for chaining.
+    pub fn with_alias(&mut self, path: impl AsRef<Path>) -> &mut Self {
+        let path = path.as_ref();
+        if path != self.path {
+            merge_unique(&mut self.aliases, [path.to_path_buf()]);
+        }
+        self
+    }
+
+    /// Probe the candidate for its Java installation properties.
+    ///
+    /// This executes the candidate's java executable, falling back to its `release`
+    /// file if the executable can't be run.
+    ///
+    /// # Returns
+    /// The parsed installation, otherwise why it couldn't be parsed.
+    pub fn probe(&self) -> Result<JavaInstall, ProbeError> {
+        self.probe_with(JavaInstall::try_parse)
+    }
+
+    /// Probe the candidate for its Java installation properties, reusing any
+    /// valid properties from the given cache.
+    ///
+    /// * `cache` - The cache to use.
+    ///
+    /// # Returns
+    /// The parsed installation, otherwise why it couldn't be parsed.
+    #[cfg(feature = "serde_json")]
+    pub fn probe_cached(&self, cache: &ProbeCache) -> Result<JavaInstall, ProbeError> {
+        self.probe_with(|e| cache.try_parse(e))
+    }
+
+    /// Read the candidate's Java installation properties from its `release` file.
+    ///
+    /// # Returns
+    /// The parsed installation, otherwise why it couldn't be parsed.
+    pub fn read_release_file(&self) -> Result<JavaInstall, ProbeError> {
+        JavaInstall::try_from_release_file(&self.path)
+            .map(|e| self.apply_metadata(e))
+    }
+
+    fn apply_metadata(&self, mut install: JavaInstall) -> JavaInstall {
+        install.metadata.extend(self.metadata.clone());
+        // The probed home may differ from the candidate, such as the JDK of a JDK 8 `jre`.
+        let aliases = [self.path.clone()].into_iter().chain(self.aliases.clone());
+        merge_unique(&mut install.aliases, aliases.filter(|e| *e != install.java_home));
+        merge_unique(&mut install.located_by, self.located_by.clone());
+        install
+    }
+
+    fn probe_with(&self, parse: impl FnOnce(PathBuf) -> Result<JavaInstall, ProbeError>) -> Result<JavaInstall, ProbeError> {
+        // Always use javaw when probing on windows, to avoid console windows being created.
+        let executable = JavaInstall::get_java_executable(&self.path, true);
+        let install = if executable.exists() {
+            parse(executable).map(|e| self.apply_metadata(e))
+        } else {
+            Err(ProbeError::MissingExecutable)
+        };
+
+        // Without a release file, the probe failure is more useful.
+        let install = install.or_else(|err| self.read_release_file().map_err(|e| if e == ProbeError::MissingReleaseFile { err } else { e }))?;
+        log_debug!("Found install for {:?} at {:?} from {:?}.", &install.lang_version, &install.java_home, &install.property_source);
+        Ok(install)
     }
 }
 
 /// A locator capable of finding Java installations somewhere on the system.
+///
+/// Locators only find candidates, which [`LocatorBuilder`] probes. To locate with a
+/// single locator, use a [`LocatorBuilder`]:
+///
+/// ```no_run
+/// use jvm_utils::locator::sdkman::SdkmanJavaLocator;
+/// use jvm_utils::locator::LocatorBuilder;
+///
+/// let installs = LocatorBuilder::new().with_locator(Box::new(SdkmanJavaLocator::new())).locate();
+/// ```
 pub trait JavaLocator {
-    /// Finds all available Java installations.
+    /// A human readable name for the locator, such as `SDKMAN!`.
+    fn name(&self) -> &'static str;
+
+    /// Finds all directories which may contain a Java installation.
+    ///
+    /// This should not execute anything, candidates are probed separately.
     ///
-    /// * `props` - The properties to filter any JVM's.
+    /// * `sysroot` - The system to search. Absolute paths, symlinks, environment
+    ///   variables and user directories should all go through it.
     ///
     /// # Returns
-    /// Some containing the JVM's found, otherwise None.
-    fn locate(&self) -> Option<Vec<JavaInstall>>;
+    /// Some containing the candidates found, otherwise None.
+    fn find_candidates(&self, sysroot: &Sysroot) -> Option<Vec<JavaCandidate>>;
+
+    /// Checks a probed installation against what the locator expected to find,
+    /// such as a version declared in a configuration file.
+    ///
+    /// This is called with every installation located, including those found by
+    /// other locators. Use the installation's metadata to recognise your own.
+    ///
+    /// * `install` - The probed installation.
+    fn verify(&self, _install: &JavaInstall) {}
+}
+
+/// Probe all candidates, using at most `limit` threads.
+///
+/// If `prefer_release_file` is set, candidates are only probed when their `release`
+/// file can't be read.
+///
+/// # Returns
+/// The probe result for each candidate, in the same order as the candidates.
+fn probe_candidates(candidates: &[JavaCandidate], limit: usize, prefer_release_file: bool, probe: impl Fn(&JavaCandidate) -> Result<JavaInstall, ProbeError> + Sync) -> Vec<Result<JavaInstall, ProbeError>> {
+    let next = AtomicUsize::new(0);
+    // Every result is replaced once its candidate is probed.
+    let results: Mutex<Vec<Result<JavaInstall, ProbeError>>> = Mutex::new(vec![Err(ProbeError::ProbeFailed); candidates.len()]);
+    thread::scope(|scope| {
+        for _ in 0..limit.min(candidates.len()) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(candidate) = candidates.get(index) else {
+                    break;
+                };
+                let install = if let Some(rejection) = &candidate.rejection {
+                    Err(rejection.clone())
+                } else if prefer_release_file {
+                    candidate.read_release_file().or_else(|_| probe(candidate))
+                } else {
+                    probe(candidate)
+                };
+                results.lock().unwrap()[index] = install;
+            });
+        }
+    });
+    results.into_inner().unwrap()
 }
 
-pub(crate) fn find_add_install(installs: &mut Vec<JavaInstall>, path: impl AsRef<Path>) -> Option<()> {
-    // Always use javaw when probing on windows, to avoid console windows being created.
-    let executable = JavaInstall::get_java_executable(&path, true);
-    if !executable.exists() {
+/// Adds the given path as a candidate, if it looks like a Java installation.
+///
+/// On macOS, the platform specific offset from the installation directory to the
+/// home directory is also tried. The home is canonicalized within the sysroot, so
+/// symlinks to an installation collapse into one candidate with the links as aliases.
+///
+/// # Returns
+/// Some containing the added candidate, or the existing candidate for the same path,
+/// otherwise None.
+///
+/// Directories which don't look like a Java installation are kept as rejected candidates.
+pub(crate) fn add_candidate<'a>(candidates: &'a mut Vec<JavaCandidate>, sysroot: &Sysroot, path: impl AsRef<Path>) -> Option<&'a mut JavaCandidate> {
+    let path = path.as_ref();
+    let Some(home) = find_home(sysroot, path) else {
+        reject_candidate(candidates, sysroot, path, ProbeError::MissingExecutable);
         return None;
+    };
+    let canonical = sysroot.canonicalize(&home).ok()?;
+
+    let mut candidate = JavaCandidate::new(canonical);
+    candidate.with_alias(home);
+    Some(merge_candidate(candidates, candidate))
+}
+
+/// Finds the home directory of the installation at the given path, the path itself
+/// or the platform specific offset from it.
+fn find_home(sysroot: &Sysroot, path: &Path) -> Option<PathBuf> {
+    [path.to_path_buf(), JavaInstall::get_home_dir(path)].into_iter()
+        .find(|e| is_java_home(sysroot, e))
+}
+
+/// Adds the given directory as a rejected candidate, if it exists.
+pub(crate) fn reject_candidate(candidates: &mut Vec<JavaCandidate>, sysroot: &Sysroot, path: &Path, rejection: ProbeError) {
+    let Ok(canonical) = sysroot.canonicalize(path) else {
+        return;
+    };
+    if !canonical.is_dir() {
+        return;
     }
+    log_debug!("Rejected directory {:?}, {}.", path, &rejection);
+
+    let mut candidate = JavaCandidate::new(canonical);
+    candidate.with_alias(path);
+    candidate.rejection = Some(rejection);
+    merge_candidate(candidates, candidate);
+}
 
-    let install = JavaInstall::parse(executable)?;
-    log_debug!("Found install for {:?} at {:?}.", &install.lang_version, &install.java_home);
+/// Adds the given candidate, merging its metadata into any existing candidate for the same path.
+fn merge_candidate(candidates: &mut Vec<JavaCandidate>, candidate: JavaCandidate) -> &mut JavaCandidate {
+    let index = match candidates.iter().position(|e| e.path == candidate.path) {
+        Some(index) => {
+            let existing = &mut candidates[index];
+            for (key, value) in candidate.metadata {
+                existing.metadata.entry(key).or_insert(value);
+            }
+            merge_unique(&mut existing.aliases, candidate.aliases);
+            merge_unique(&mut existing.located_by, candidate.located_by);
+            // A candidate only stays rejected if every locator rejected it.
+            if candidate.rejection.is_none() {
+                existing.rejection = None;
+            }
+            index
+        }
+        None => {
+            candidates.push(candidate);
+            candidates.len() - 1
+        }
+    };
+    &mut candidates[index]
+}
 
-    add_install(installs, Some(install));
-    Some(())
+/// Checks if the given directory looks like the home directory of a Java installation.
+///
+/// This is the case if it contains a java executable for this platform, or a `release`
+/// file next to a bin directory, which may be for another platform. Symlinks are
+/// resolved within the sysroot, those which can't be resolved are never followed.
+pub(crate) fn is_java_home(sysroot: &Sysroot, path: impl AsRef<Path>) -> bool {
+    let Ok(path) = sysroot.canonicalize(path) else {
+        return false;
+    };
+    sysroot.exists(JavaInstall::get_java_executable(&path, true)) || (sysroot.is_file(path.join("release")) && sysroot.is_dir(path.join("bin")))
 }
 
-fn add_install(installs: &mut Vec<JavaInstall>, install: Option<JavaInstall>) {
-    let to_add: Vec<JavaInstall> = install.into_iter()
-        .filter(|e| !installs.iter().any(|existing| existing.java_home.eq(&e.java_home)))
-        .collect();
+fn add_install(installs: &mut Vec<JavaInstall>, install: JavaInstall) {
+    match installs.iter_mut().find(|existing| existing.java_home.eq(&install.java_home)) {
+        Some(existing) => {
+            for (key, value) in install.metadata {
+                existing.metadata.entry(key).or_insert(value);
+            }
+            let java_home = existing.java_home.clone();
+            merge_unique(&mut existing.aliases, install.aliases.into_iter().filter(|e| *e != java_home));
+            merge_unique(&mut existing.located_by, install.located_by);
+        }
+        None => installs.push(install),
+    }
+}
 
-    installs.extend(to_add);
+/// Moves a candidate to its canonical home, keeping the path it was found at as an
+/// alias, and records the locator which found it.
+fn resolve_candidate(sysroot: &Sysroot, locator: &str, mut candidate: JavaCandidate) -> JavaCandidate {
+    if let Ok(path) = sysroot.canonicalize(&candidate.path) {
+        if path != candidate.path {
+            let alias = mem::replace(&mut candidate.path, path);
+            merge_unique(&mut candidate.aliases, [alias]);
+        }
+    }
+    merge_unique(&mut candidate.located_by, [locator.to_string()]);
+    candidate
 }
 
-pub(crate) fn list_dir(dir: impl AsRef<Path>) -> Vec<fs::DirEntry> {
-    fs::read_dir(dir.as_ref())
+/// Appends the values which aren't already present, keeping their order.
+fn merge_unique<T: PartialEq>(vec: &mut Vec<T>, values: impl IntoIterator<Item = T>) {
+    for value in values {
+        if !vec.contains(&value) {
+            vec.push(value);
+        }
+    }
+}
+
+/// Lists the entries of a directory, sorted by path so results are stable between runs.
+///
+/// The directory is canonicalized within the sysroot first, so the entries are
+/// within the canonical directory.
+pub(crate) fn list_dir(sysroot: &Sysroot, dir: impl AsRef<Path>) -> Vec<fs::DirEntry> {
+    let Ok(dir) = sysroot.canonicalize(dir) else {
+        return Vec::new();
+    };
+    let mut entries: Vec<fs::DirEntry> = fs::read_dir(dir)
         .ok()
         .into_iter()
         .flatten()
         .flatten()
-        .collect()
+        .collect();
+    entries.sort_by_key(|e| e.path());
+    entries
 }
 
-pub(crate) fn scan_folder(vec: &mut Vec<JavaInstall>, dir: impl AsRef<Path>) {
+pub(crate) fn scan_folder(vec: &mut Vec<JavaCandidate>, sysroot: &Sysroot, dir: impl AsRef<Path>) {
     log_debug!("Scanning folder for JVM's: {:?}", dir.as_ref());
-    for entry in list_dir(dir) {
+    for entry in list_dir(sysroot, dir) {
         let candidate_path = entry.path();
-        if !candidate_path.is_dir() {
+        if !sysroot.is_dir(&candidate_path) {
             continue;
         }
-        if find_add_install(vec, &candidate_path).is_some() {
-            continue;
+        add_candidate_or_child(vec, sysroot, candidate_path);
+    }
+}
+
+/// Adds the given path as a candidate, or its only child if the path itself does not
+/// look like a Java installation. Archives commonly extract to a single directory.
+///
+/// # Returns
+/// Some containing the added candidate, otherwise None.
+pub(crate) fn add_candidate_or_child<'a>(vec: &'a mut Vec<JavaCandidate>, sysroot: &Sysroot, path: impl AsRef<Path>) -> Option<&'a mut JavaCandidate> {
+    let path = path.as_ref();
+    if find_home(sysroot, path).is_none() {
+        let inners = list_dir(sysroot, path);
+        if let [inner] = inners.as_slice() {
+            if find_home(sysroot, &inner.path()).is_some() {
+                return add_candidate(vec, sysroot, inner.path());
+            }
         }
+    }
+    add_candidate(vec, sysroot, path)
+}
+
+/// Scans a folder for JVM's like [`scan_folder`], resolving symlinked aliases such as
+/// `latest` to their canonical homes so each install is only found once.
+///
+/// The name of the entry each install was found under is recorded as metadata with
+/// the given key, preferring the real directory name over any alias. Where several
+/// aliases point outside the folder to the same install, the longest name is kept.
+///
+/// * `sysroot` - The system being searched, to resolve symlinks within.
+/// * `dir` - The directory to scan.
+/// * `metadata_key` - The metadata key for the entry name.
+pub(crate) fn scan_aliased_folder(vec: &mut Vec<JavaCandidate>, sysroot: &Sysroot, dir: impl AsRef<Path>, metadata_key: &str) {
+    let Ok(dir) = sysroot.canonicalize(dir) else {
+        return;
+    };
+    let entry_name = |path: &PathBuf| path.strip_prefix(&dir).ok()
+        .and_then(|e| e.components().next())
+        .map(|e| e.as_os_str().to_string_lossy().into_owned());
+
+    let mut found: Vec<JavaCandidate> = Vec::new();
+    scan_folder(&mut found, sysroot, &dir);
+    for candidate in found {
+        let name = entry_name(&candidate.path)
+            .or_else(|| candidate.aliases.iter().rev().filter_map(entry_name).max_by_key(String::len));
 
-        let inners = list_dir(candidate_path);
-        if inners.len() == 1 {
-            find_add_install(vec, inners[0].path());
+        let added = merge_candidate(vec, candidate);
+        if let Some(name) = name {
+            let existing = added.metadata.entry(metadata_key.into()).or_default();
+            if name.len() > existing.len() {
+                *existing = name;
+            }
         }
     }
 }
@@ -0,0 +1,36 @@
+use crate::locator::sysroot::Sysroot;
+use crate::locator::{scan_aliased_folder, JavaCandidate, JavaLocator};
+use std::path::PathBuf;
+
+/// The metadata key for the asdf version name of an install, such as `temurin-17.0.10+7`.
+pub const ASDF_VERSION: &str = "asdf.version";
+
+/// A JavaLocator capable of locating JVM's installed by the asdf java plugin.
+#[derive(Default)]
+pub struct AsdfJavaLocator {}
+
+impl AsdfJavaLocator {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    fn data_dirs(sysroot: &Sysroot) -> Vec<PathBuf> {
+        sysroot.dirs_from_env_or_home("ASDF_DATA_DIR", ".asdf")
+    }
+}
+
+impl JavaLocator for AsdfJavaLocator {
+    fn name(&self) -> &'static str {
+        "asdf"
+    }
+
+    fn find_candidates(&self, sysroot: &Sysroot) -> Option<Vec<JavaCandidate>> {
+        let mut vec: Vec<JavaCandidate> = Vec::new();
+        for dir in Self::data_dirs(sysroot) {
+            let dir = dir.join("installs/java");
+            log_debug!("Searching for JVM's installed by asdf in path: {:?}", &dir);
+            scan_aliased_folder(&mut vec, sysroot, &dir, ASDF_VERSION);
+        }
+        Some(vec)
+    }
+}
@@ -0,0 +1,193 @@
+use crate::extract::extract_java_properties;
+use crate::install::{JavaInstall, ProbeError};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+
+/// An on-disk cache of the properties extracted from java executables.
+///
+/// Entries are keyed on the executable path, and are only valid while the
+/// executable's size and modification time are unchanged.
+pub struct ProbeCache {
+    path: PathBuf,
+    entries: Mutex<BTreeMap<PathBuf, CacheEntry>>,
+    refresh: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+struct CacheEntry {
+    size: u64,
+    modified_secs: u64,
+    modified_nanos: u32,
+    properties: HashMap<String, String>,
+}
+
+impl CacheEntry {
+    fn matches(&self, other: &CacheEntry) -> bool {
+        self.size == other.size && self.modified_secs == other.modified_secs && self.modified_nanos == other.modified_nanos
+    }
+
+    fn for_executable(executable: &Path) -> Option<Self> {
+        let metadata = fs::metadata(executable).ok()?;
+        let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+        Some(Self {
+            size: metadata.len(),
+            modified_secs: modified.as_secs(),
+            modified_nanos: modified.subsec_nanos(),
+            properties: HashMap::new(),
+        })
+    }
+}
+
+impl ProbeCache {
+    /// The default location of the cache file, within the user's cache directory.
+    ///
+    /// # Returns
+    /// Some containing the path, otherwise None if the cache directory is unknown.
+    pub fn default_path() -> Option<PathBuf> {
+        Some(dirs::cache_dir()?.join("jvm_utils").join("probe_cache.json"))
+    }
+
+    /// Create an empty cache, which will be saved to the given path.
+    ///
+    /// * `path` - The path of the cache file.
+    pub fn empty(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            entries: Mutex::new(BTreeMap::new()),
+            refresh: false,
+        }
+    }
+
+    /// Probe every executable again instead of using its cached properties.
+    ///
+    /// Only the entries of executables probed are replaced, others are kept.
+    ///
+    /// # Returns
+    /// The same cache, for chaining.
+    pub fn refreshing(mut self) -> Self {
+        self.refresh = true;
+        self
+    }
+
+    /// Load the cache from the given path.
+    ///
+    /// A missing or unreadable cache file results in an empty cache.
+    ///
+    /// * `path` - The path of the cache file.
+    pub fn load(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        let cache = Self::empty(path);
+        if !path.exists() {
+            return cache;
+        }
+
+        let entries = fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|e| serde_json::from_str(&e).map_err(|e| e.to_string()));
+        match entries {
+            Ok(entries) => *cache.entries.lock().unwrap() = entries,
+            Err(err) => log_warn!("Failed to read probe cache {:?}, it will be ignored. {}", path, err),
+        }
+        cache
+    }
+
+    /// Save the cache to disk.
+    pub fn save(&self) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let entries = self.entries.lock().unwrap();
+        fs::write(&self.path, serde_json::to_string_pretty(&*entries)?)
+    }
+
+    /// Parse a Java installation from the given executable, using the cached
+    /// properties if they are still valid.
+    ///
+    /// * `executable` - The executable path.
+    ///
+    /// # Returns
+    /// Maybe a JavaInstall with extracted properties and attributes.
+    pub fn parse(&self, executable: impl AsRef<Path>) -> Option<JavaInstall> {
+        self.try_parse(executable).ok()
+    }
+
+    /// Parse a Java installation from the given executable, using the cached
+    /// properties if they are still valid.
+    ///
+    /// * `executable` - The executable path.
+    ///
+    /// # Returns
+    /// The JavaInstall, otherwise why it couldn't be parsed.
+    pub fn try_parse(&self, executable: impl AsRef<Path>) -> Result<JavaInstall, ProbeError> {
+        let executable = executable.as_ref();
+        let mut current = CacheEntry::for_executable(executable).ok_or(ProbeError::MissingExecutable)?;
+
+        let cached = self.entries.lock().unwrap().get(executable).cloned();
+        if let Some(cached) = cached.filter(|e| !self.refresh && e.matches(&current)) {
+            log_debug!("Using cached properties for {:?}.", executable);
+            return JavaInstall::try_from_properties(&cached.properties);
+        }
+
+        current.properties = extract_java_properties(executable, JavaInstall::PROPERTIES).ok_or(ProbeError::ProbeFailed)?;
+        let install = JavaInstall::try_from_properties(&current.properties);
+        // Only cache successful probes, a failure may be transient.
+        if install.is_ok() {
+            self.entries.lock().unwrap().insert(executable.to_path_buf(), current);
+        }
+        install
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+    use tempfile::TempDir;
+
+    fn create_java(home: &Path) -> PathBuf {
+        let executable = home.join("bin/java");
+        fs::create_dir_all(home.join("bin")).unwrap();
+        fs::write(&executable, concat!(
+            "#!/bin/sh\n",
+            "echo java.home=/opt/jdk\n",
+            "echo java.version=17.0.10\n",
+            "echo java.vendor=Eclipse Adoptium\n",
+            "echo os.arch=amd64\n",
+            "echo java.vm.name=OpenJDK 64-Bit Server VM\n",
+            "echo java.vm.version=17.0.10+7\n",
+            "echo java.runtime.name=OpenJDK Runtime Environment\n",
+            "echo java.runtime.version=17.0.10+7\n",
+        )).unwrap();
+        fs::set_permissions(&executable, fs::Permissions::from_mode(0o755)).unwrap();
+        executable
+    }
+
+    #[test]
+    fn refresh_keeps_other_entries() {
+        let temp = TempDir::new().unwrap();
+        let executable = create_java(&temp.path().join("jdk"));
+        let other = temp.path().join("other/bin/java");
+
+        // A stale entry for the executable, which still matches its metadata.
+        let mut stale = CacheEntry::for_executable(&executable).unwrap();
+        stale.properties.insert("java.version".into(), "11.0.22".into());
+        let cache = ProbeCache::empty(temp.path().join("cache.json"));
+        cache.entries.lock().unwrap().insert(executable.clone(), stale);
+        cache.entries.lock().unwrap().insert(other.clone(), CacheEntry::for_executable(&executable).unwrap());
+        cache.save().unwrap();
+
+        let cache = ProbeCache::load(temp.path().join("cache.json")).refreshing();
+        let install = cache.try_parse(&executable).unwrap();
+        assert_eq!(install.runtime_version, "17.0.10+7");
+        cache.save().unwrap();
+
+        let entries = ProbeCache::load(temp.path().join("cache.json")).entries.into_inner().unwrap();
+        assert_eq!(entries[&executable].properties["java.version"], "17.0.10");
+        assert!(entries.contains_key(&other));
+    }
+}
@@ -0,0 +1,122 @@
+use crate::locator::sysroot::{Sysroot, UserDirs};
+use crate::locator::{add_candidate_or_child, list_dir, JavaCandidate, JavaLocator};
+use std::path::{Path, PathBuf};
+
+/// The metadata key for the Coursier JVM id of an install, such as `adopt:1.11.0-7`.
+pub const COURSIER_JVM: &str = "coursier.jvm";
+/// The metadata key for the url of the archive Coursier extracted an install from.
+pub const COURSIER_URL: &str = "coursier.url";
+
+/// The extensions of archives which Coursier extracts into its archive cache.
+const ARCHIVE_EXTENSIONS: &[&str] = &[".tar.gz", ".tgz", ".tar.xz", ".txz", ".tar.bz2", ".tbz2", ".tar", ".zip"];
+
+/// How deep to descend into the archive cache looking for archives.
+/// Archives are stored at their url path, which is rarely more than 10 segments.
+const MAX_ARCHIVE_DEPTH: usize = 16;
+
+/// A JavaLocator capable of locating JVM's installed by Coursier (`cs java`).
+///
+/// Current versions of Coursier extract JVM's into the archive cache, at a path
+/// derived from the url they were downloaded from. Older versions used a dedicated
+/// JVM cache, with a directory for each JVM index id.
+#[derive(Default)]
+pub struct CoursierJavaLocator {}
+
+impl CoursierJavaLocator {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    fn cache_dir(user: &UserDirs) -> PathBuf {
+        // Coursier only lowercases its cache directory name on Linux.
+        if cfg!(target_os = "windows") {
+            user.cache.join("Coursier/cache")
+        } else if cfg!(target_os = "macos") {
+            user.cache.join("Coursier")
+        } else {
+            user.cache.join("coursier")
+        }
+    }
+
+    fn cache_dirs_or_var(sysroot: &Sysroot, var: &str, name: &str) -> Vec<PathBuf> {
+        match sysroot.var_os(var) {
+            Some(dir) => vec![PathBuf::from(dir)],
+            None => sysroot.users().iter().map(|e| Self::cache_dir(e).join(name)).collect(),
+        }
+    }
+}
+
+impl JavaLocator for CoursierJavaLocator {
+    fn name(&self) -> &'static str {
+        "Coursier"
+    }
+
+    fn find_candidates(&self, sysroot: &Sysroot) -> Option<Vec<JavaCandidate>> {
+        let mut vec: Vec<JavaCandidate> = Vec::new();
+
+        for dir in Self::cache_dirs_or_var(sysroot, "COURSIER_ARCHIVE_CACHE", "arc") {
+            log_debug!("Searching for JVM's installed by Coursier in archive cache: {:?}", &dir);
+            // Entries are listed within the canonical directory, the url is relative to it.
+            let Ok(dir) = sysroot.canonicalize(&dir) else {
+                continue;
+            };
+            scan_archive_cache(&mut vec, sysroot, &dir, &dir, 0);
+        }
+
+        for dir in Self::cache_dirs_or_var(sysroot, "COURSIER_JVM_CACHE", "jvm") {
+            log_debug!("Searching for JVM's installed by Coursier in JVM cache: {:?}", &dir);
+            for entry in list_dir(sysroot, &dir) {
+                let path = entry.path();
+                // Entries are named after the JVM index id, `<name>@<version>`.
+                let Some((name, version)) = path.file_name().and_then(|e| e.to_str()).and_then(|e| e.rsplit_once('@')) else {
+                    continue;
+                };
+                let id = format!("{}:{}", name, version);
+                if let Some(candidate) = add_candidate_or_child(&mut vec, sysroot, &path) {
+                    candidate.with_metadata(COURSIER_JVM, id);
+                }
+            }
+        }
+        Some(vec)
+    }
+}
+
+/// Recursively scans the archive cache for extracted archives containing a JVM.
+fn scan_archive_cache(vec: &mut Vec<JavaCandidate>, sysroot: &Sysroot, root: &Path, dir: &Path, depth: usize) {
+    if depth > MAX_ARCHIVE_DEPTH {
+        return;
+    }
+
+    for entry in list_dir(sysroot, dir) {
+        let path = entry.path();
+        if !entry.file_type().is_ok_and(|e| e.is_dir()) {
+            continue;
+        }
+
+        let is_archive = path.file_name()
+            .and_then(|e| e.to_str())
+            .is_some_and(|name| ARCHIVE_EXTENSIONS.iter().any(|ext| name.ends_with(ext)));
+        if !is_archive {
+            scan_archive_cache(vec, sysroot, root, &path, depth + 1);
+            continue;
+        }
+
+        if let Some(candidate) = add_candidate_or_child(vec, sysroot, &path) {
+            if let Some(url) = archive_url(root, &path) {
+                candidate.with_metadata(COURSIER_URL, url);
+            }
+        }
+    }
+}
+
+/// Rebuilds the url an archive was downloaded from, using its path in the archive cache.
+///
+/// The first path segment is the url scheme, for example `arc/https/github.com/...`.
+fn archive_url(root: &Path, archive: &Path) -> Option<String> {
+    let mut segments = archive.strip_prefix(root).ok()?
+        .components()
+        .map(|e| e.as_os_str().to_string_lossy().into_owned());
+    let scheme = segments.next()?;
+    let rest: Vec<String> = segments.collect();
+    Some(format!("{}://{}", scheme, rest.join("/")))
+}
@@ -0,0 +1,46 @@
+use crate::install::ProbeError;
+use std::fmt;
+use std::path::PathBuf;
+
+/// Why a candidate wasn't located.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum RejectionReason {
+    /// The candidate's properties couldn't be read.
+    Probe(ProbeError),
+    /// The installation was rejected by a filter, such as `JDK only`.
+    Filtered(String),
+}
+
+impl fmt::Display for RejectionReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RejectionReason::Probe(err) => err.fmt(f),
+            RejectionReason::Filtered(filter) => write!(f, "filtered by {}", filter),
+        }
+    }
+}
+
+/// What happened to a candidate examined while locating.
+#[derive(Debug, Clone)]
+pub struct CandidateDiagnostic {
+    pub path: PathBuf,
+    /// The names of the locators which found the candidate.
+    pub located_by: Vec<String>,
+    /// Why the candidate wasn't located, None if it was.
+    pub rejection: Option<RejectionReason>,
+}
+
+/// A report of every candidate examined while locating, in discovery order.
+///
+/// See `LocatorBuilder::locate_with_diagnostics`.
+#[derive(Debug, Clone, Default)]
+pub struct Diagnostics {
+    pub candidates: Vec<CandidateDiagnostic>,
+}
+
+impl Diagnostics {
+    /// Gets the candidates which weren't located.
+    pub fn rejected(&self) -> impl Iterator<Item = &CandidateDiagnostic> {
+        self.candidates.iter().filter(|e| e.rejection.is_some())
+    }
+}
@@ -0,0 +1,268 @@
+use crate::install::JavaInstall;
+use crate::locator::sysroot::Sysroot;
+use crate::locator::{add_candidate, is_java_home, list_dir, JavaCandidate, JavaLocator};
+use regex::Regex;
+use std::collections::HashSet;
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// The metadata key for the root directory an install was found under.
+pub const DIRECTORY_ROOT: &str = "directory.root";
+
+/// The environment variable listing extra roots, separated like `PATH`.
+pub const PATHS_VAR: &str = "JVM_UTILS_PATHS";
+
+/// The default maximum depth, enough for archives which extract to a single directory.
+const DEFAULT_MAX_DEPTH: usize = 2;
+
+/// A JavaLocator capable of locating JVM's anywhere within the given directory trees.
+///
+/// Each root is walked up to a maximum depth, stopping at the first Java installation
+/// on each branch. Directories may be included or excluded with glob patterns.
+/// Within a sysroot, the roots are re-rooted under it.
+///
+/// ```no_run
+/// use jvm_utils::locator::directory::DirectoryJavaLocator;
+/// use jvm_utils::locator::LocatorBuilder;
+///
+/// let mut directory = DirectoryJavaLocator::new();
+/// directory.with_root("/srv/jdks").max_depth(3).exclude("*-debug");
+///
+/// let installs = LocatorBuilder::new().with_locator(Box::new(directory)).locate();
+/// ```
+pub struct DirectoryJavaLocator {
+    roots: Vec<PathBuf>,
+    env_vars: Vec<String>,
+    max_depth: usize,
+    include: Vec<Regex>,
+    exclude: Vec<Regex>,
+    follow_symlinks: bool,
+}
+
+impl Default for DirectoryJavaLocator {
+    fn default() -> Self {
+        Self {
+            roots: Vec::new(),
+            env_vars: Vec::new(),
+            max_depth: DEFAULT_MAX_DEPTH,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            follow_symlinks: false,
+        }
+    }
+}
+
+impl DirectoryJavaLocator {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Create a locator which searches the roots listed in [`PATHS_VAR`].
+    pub fn from_env() -> Self {
+        let mut locator = Self::new();
+        locator.with_roots_from_env(PATHS_VAR);
+        locator
+    }
+
+    /// Search the given directory.
+    ///
+    /// * `root` - The directory to search.
+    pub fn with_root(&mut self, root: impl AsRef<Path>) -> &mut Self {
+        self.roots.push(root.as_ref().to_path_buf());
+        self
+    }
+
+    /// Search the directories listed in the given environment variable, separated
+    /// like `PATH`. The variable is read when searching.
+    ///
+    /// * `var` - The name of the environment variable.
+    pub fn with_roots_from_env(&mut self, var: &str) -> &mut Self {
+        self.env_vars.push(var.into());
+        self
+    }
+
+    /// Limit how deep each root is searched, a depth of 1 only searches the root's
+    /// immediate children.
+    ///
+    /// Defaults to 2.
+    ///
+    /// * `depth` - The maximum depth.
+    pub fn max_depth(&mut self, depth: usize) -> &mut Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Only locate installations matching the given glob pattern.
+    ///
+    /// Patterns containing a `/` are matched against the path relative to the root,
+    /// otherwise against the directory name. `*` and `?` match within a single path
+    /// component, `**` matches across components and `**/` matches any number of
+    /// leading directories, including none.
+    ///
+    /// * `glob` - The glob pattern, such as `jdk-*`.
+    pub fn include(&mut self, glob: &str) -> &mut Self {
+        self.include.extend(glob_regex(glob));
+        self
+    }
+
+    /// Skip directories matching the given glob pattern, and everything within them.
+    ///
+    /// Patterns are matched as described in [`DirectoryJavaLocator::include`].
+    ///
+    /// * `glob` - The glob pattern, such as `*-debug`.
+    pub fn exclude(&mut self, glob: &str) -> &mut Self {
+        self.exclude.extend(glob_regex(glob));
+        self
+    }
+
+    /// Follow symlinked directories within the roots. The roots themselves are
+    /// always followed.
+    ///
+    /// Symlinks are skipped by default, so aliases such as `latest` aren't located twice.
+    pub fn follow_symlinks(&mut self) -> &mut Self {
+        self.follow_symlinks = true;
+        self
+    }
+
+    fn scan(&self, vec: &mut Vec<JavaCandidate>, sysroot: &Sysroot, visited: &mut HashSet<PathBuf>, root: &Path, dir: &Path, depth: usize) {
+        let Ok(canonical) = sysroot.canonicalize(dir) else {
+            return;
+        };
+        // Guard against symlink loops.
+        if !visited.insert(canonical) {
+            return;
+        }
+
+        let relative = dir.strip_prefix(root).unwrap_or(dir);
+        if depth > 0 && self.exclude.iter().any(|e| glob_matches(e, relative)) {
+            return;
+        }
+
+        if is_java_home(sysroot, dir) || is_java_home(sysroot, JavaInstall::get_home_dir(dir)) {
+            if depth == 0 || self.include.is_empty() || self.include.iter().any(|e| glob_matches(e, relative)) {
+                if let (Some(candidate), Some(root)) = (add_candidate(vec, sysroot, dir), sysroot.target_path(root)) {
+                    candidate.metadata.entry(DIRECTORY_ROOT.into()).or_insert_with(|| root.to_string_lossy().into_owned());
+                }
+            }
+            return;
+        }
+
+        if depth >= self.max_depth {
+            return;
+        }
+        for entry in list_dir(sysroot, dir) {
+            let mut path = entry.path();
+            if entry.file_type().is_ok_and(|e| e.is_symlink()) {
+                // Resolve the link ourselves, so it stays within any sysroot.
+                let Some(target) = sysroot.canonicalize(&path).ok().filter(|_| self.follow_symlinks) else {
+                    continue;
+                };
+                path = target;
+            }
+            if path.is_dir() {
+                self.scan(vec, sysroot, visited, root, &path, depth + 1);
+            }
+        }
+    }
+}
+
+impl JavaLocator for DirectoryJavaLocator {
+    fn name(&self) -> &'static str {
+        "Directory"
+    }
+
+    fn find_candidates(&self, sysroot: &Sysroot) -> Option<Vec<JavaCandidate>> {
+        let mut roots = self.roots.clone();
+        for var in &self.env_vars {
+            if let Some(paths) = sysroot.var_os(var) {
+                roots.extend(env::split_paths(&paths).filter(|e| !e.as_os_str().is_empty()));
+            }
+        }
+
+        let mut vec: Vec<JavaCandidate> = Vec::new();
+        let mut visited: HashSet<PathBuf> = HashSet::new();
+        for root in roots.iter().map(|e| sysroot.path(e)) {
+            log_debug!("Searching for JVM's in directory: {:?}", &root);
+            // Entries are listed within the canonical directory, match globs against it.
+            let Ok(root) = sysroot.canonicalize(&root) else {
+                continue;
+            };
+            self.scan(&mut vec, sysroot, &mut visited, &root, &root, 0);
+        }
+        Some(vec)
+    }
+}
+
+/// Converts a glob pattern into an anchored regex.
+///
+/// Patterns without a `/` match the last path component.
+fn glob_regex(glob: &str) -> Option<Regex> {
+    let mut re = String::from(if glob.contains('/') { "^" } else { "(^|/)" });
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.next_if_eq(&'/').is_some() {
+                    re.push_str("(.*/)?");
+                } else {
+                    re.push_str(".*");
+                }
+            }
+            '*' => re.push_str("[^/]*"),
+            '?' => re.push_str("[^/]"),
+            c => re.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    re.push('$');
+    Regex::new(&re).ok()
+}
+
+fn glob_matches(re: &Regex, relative: &Path) -> bool {
+    // Match with `/` separators on every platform.
+    let path: Vec<String> = relative.components().map(|e| e.as_os_str().to_string_lossy().into_owned()).collect();
+    re.is_match(&path.join("/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(glob: &str, path: &str) -> bool {
+        glob_matches(&glob_regex(glob).unwrap(), Path::new(path))
+    }
+
+    #[test]
+    fn matches_names_at_any_depth() {
+        assert!(matches("jdk-*", "jdk-17"));
+        assert!(matches("jdk-*", "vendors/jdk-17"));
+        assert!(!matches("jdk-*", "openjdk-17"));
+        assert!(!matches("jdk-*", "jdk-17/bin"));
+        assert!(matches("jdk-1?", "jdk-17"));
+        assert!(!matches("jdk-1?", "jdk-1/7"));
+    }
+
+    #[test]
+    fn anchors_paths_at_root() {
+        assert!(matches("vendors/*", "vendors/jdk-17"));
+        assert!(!matches("vendors/*", "old/vendors/jdk-17"));
+        assert!(!matches("vendors/*", "vendors/jdk-17/bin"));
+    }
+
+    #[test]
+    fn matches_across_components() {
+        assert!(matches("vendors/**", "vendors/a/b/jdk-17"));
+        assert!(matches("**/jdk-17", "jdk-17"));
+        assert!(matches("**/jdk-17", "a/b/jdk-17"));
+        assert!(!matches("**/jdk-17", "a/b/openjdk-17"));
+        assert!(matches("a/**/jdk", "a/jdk"));
+        assert!(matches("a/**/jdk", "a/b/c/jdk"));
+        assert!(matches("a/**jdk", "a/b/openjdk"));
+    }
+
+    #[test]
+    fn escapes_regex_characters() {
+        assert!(matches("jdk+17.0", "jdk+17.0"));
+        assert!(!matches("jdk+17.0", "jdkk17x0"));
+    }
+}
@@ -0,0 +1,132 @@
+use crate::locator::sysroot::Sysroot;
+use crate::locator::{add_candidate, add_candidate_or_child, list_dir, JavaCandidate, JavaLocator};
+use std::path::{Path, PathBuf};
+
+/// The metadata key for the editor which bundles an install, such as `Eclipse` or `VS Code`.
+pub const EDITOR: &str = "editor";
+/// The metadata key for the editor plugin or extension which bundles an install,
+/// such as `redhat.java-1.28.1-linux-x64`.
+pub const EDITOR_BUNDLE: &str = "editor.bundle";
+
+/// The prefix of Eclipse JustJ JRE plugins, both the `full` and `minimal` variants.
+const JUSTJ_PREFIX: &str = "org.eclipse.justj.openjdk.hotspot.jre.";
+
+/// The prefix of the Red Hat Java extension for VS Code.
+const VSCODE_JAVA_PREFIX: &str = "redhat.java-";
+
+/// How deep to search for Eclipse `plugins` directories. The Eclipse installer
+/// puts installs at `~/eclipse/<package>/eclipse/plugins`.
+const MAX_ECLIPSE_DEPTH: usize = 3;
+
+/// VS Code and its derivatives, relative to the user's home, with their editor name.
+const VSCODE_DIRS: &[(&str, &str)] = &[
+    (".vscode/extensions", "VS Code"),
+    (".vscode-insiders/extensions", "VS Code Insiders"),
+    (".vscode-server/extensions", "VS Code Server"),
+    (".vscode-oss/extensions", "VSCodium"),
+];
+
+/// System wide Eclipse install locations.
+#[cfg(target_os = "linux")]
+const ECLIPSE_DIRS: &[&str] = &["/opt", "/usr/lib/eclipse", "/snap/eclipse/current"];
+#[cfg(target_os = "macos")]
+const ECLIPSE_DIRS: &[&str] = &["/Applications"];
+#[cfg(target_os = "windows")]
+const ECLIPSE_DIRS: &[&str] = &["C:/Program Files", "C:/eclipse"];
+
+/// A JavaLocator capable of locating JVM's bundled with editors.
+///
+/// This covers Eclipse JustJ JRE plugins, in Eclipse installs or the shared p2
+/// bundle pool, and the JRE bundled with the Red Hat Java extension for VS Code.
+#[derive(Default)]
+pub struct EditorJavaLocator {}
+
+impl EditorJavaLocator {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    fn eclipse_dirs(sysroot: &Sysroot) -> Vec<PathBuf> {
+        let mut dirs: Vec<PathBuf> = Vec::new();
+        for user in sysroot.users() {
+            dirs.push(user.home.join("eclipse"));
+            dirs.push(user.home.join(".p2/pool"));
+            dirs.push(user.home.join("Applications"));
+        }
+        dirs.extend(ECLIPSE_DIRS.iter().map(|e| sysroot.path(e)));
+        dirs
+    }
+}
+
+impl JavaLocator for EditorJavaLocator {
+    fn name(&self) -> &'static str {
+        "Editor"
+    }
+
+    fn find_candidates(&self, sysroot: &Sysroot) -> Option<Vec<JavaCandidate>> {
+        let mut vec: Vec<JavaCandidate> = Vec::new();
+        for dir in Self::eclipse_dirs(sysroot) {
+            log_debug!("Searching for JVM's bundled with Eclipse in path: {:?}", &dir);
+            scan_eclipse_plugins(&mut vec, sysroot, &dir, 0);
+        }
+
+        for user in sysroot.users() {
+            for (dir, editor) in VSCODE_DIRS {
+                let dir = user.home.join(dir);
+                log_debug!("Searching for JVM's bundled with {} extensions in path: {:?}", editor, &dir);
+                scan_vscode_extensions(&mut vec, sysroot, &dir, editor);
+            }
+        }
+        Some(vec)
+    }
+}
+
+/// Adds the JRE's bundled with each Red Hat Java extension in the given extensions directory.
+fn scan_vscode_extensions(vec: &mut Vec<JavaCandidate>, sysroot: &Sysroot, dir: &Path, editor: &str) {
+    for extension in bundle_entries(sysroot, dir, VSCODE_JAVA_PREFIX) {
+        // The extension keeps the JRE in a versioned directory, `jre/17.0.10-linux-x86_64`.
+        for jre in list_dir(sysroot, extension.join("jre")) {
+            if let Some(candidate) = add_candidate_or_child(vec, sysroot, jre.path()) {
+                label(candidate, editor, &extension);
+            }
+        }
+    }
+}
+
+/// Recursively searches for Eclipse `plugins` directories, adding any JustJ JRE's.
+fn scan_eclipse_plugins(vec: &mut Vec<JavaCandidate>, sysroot: &Sysroot, dir: &Path, depth: usize) {
+    let plugins = dir.join("plugins");
+    if sysroot.is_dir(&plugins) {
+        for plugin in bundle_entries(sysroot, &plugins, JUSTJ_PREFIX) {
+            if let Some(candidate) = add_candidate(vec, sysroot, plugin.join("jre")) {
+                label(candidate, "Eclipse", &plugin);
+            }
+        }
+        return;
+    }
+
+    if depth >= MAX_ECLIPSE_DEPTH {
+        return;
+    }
+    for entry in list_dir(sysroot, dir) {
+        let path = entry.path();
+        if sysroot.is_dir(&path) {
+            scan_eclipse_plugins(vec, sysroot, &path, depth + 1);
+        }
+    }
+}
+
+/// Lists the plugin or extension directories in the given directory with the given prefix.
+fn bundle_entries(sysroot: &Sysroot, dir: &Path, prefix: &str) -> Vec<PathBuf> {
+    list_dir(sysroot, dir).into_iter()
+        .map(|e| e.path())
+        .filter(|e| sysroot.is_dir(e) && e.file_name().and_then(|e| e.to_str()).is_some_and(|e| e.starts_with(prefix)))
+        .collect()
+}
+
+fn label(candidate: &mut JavaCandidate, editor: &str, bundle: &Path) {
+    candidate.metadata.entry(EDITOR.into()).or_insert_with(|| editor.into());
+    if let Some(name) = bundle.file_name() {
+        candidate.metadata.entry(EDITOR_BUNDLE.into()).or_insert_with(|| name.to_string_lossy().into_owned());
+    }
+}
@@ -0,0 +1,89 @@
+use crate::install::JavaInstall;
+use crate::locator::sysroot::Sysroot;
+use crate::locator::{add_candidate, JavaCandidate, JavaLocator};
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// The environment variables which may point to the home directory of a JVM.
+const HOME_VARS: &[&str] = &["JAVA_HOME", "JDK_HOME"];
+
+/// A JavaLocator capable of locating JVM's referenced by the environment.
+///
+/// This includes the `JAVA_HOME` and `JDK_HOME` variables, along with any `java`
+/// executable on the `PATH`. Nothing is found within a sysroot, as it has no environment.
+#[derive(Default)]
+pub struct EnvironmentJavaLocator {}
+
+impl EnvironmentJavaLocator {
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl JavaLocator for EnvironmentJavaLocator {
+    fn name(&self) -> &'static str {
+        "Environment"
+    }
+
+    fn find_candidates(&self, sysroot: &Sysroot) -> Option<Vec<JavaCandidate>> {
+        let mut vec: Vec<JavaCandidate> = Vec::new();
+        for var in HOME_VARS {
+            if let Some(home) = sysroot.var_os(var) {
+                log_debug!("Searching for JVM referenced by {} environment variable: {:?}", var, &home);
+                add_candidate(&mut vec, sysroot, home);
+            }
+        }
+
+        if let Some(path) = sysroot.var_os("PATH") {
+            log_debug!("Searching for JVM's on the PATH.");
+            for dir in env::split_paths(&path) {
+                let executable = dir.join("java").with_extension(env::consts::EXE_EXTENSION);
+                let Some(home) = resolve_home(sysroot, &executable) else {
+                    continue;
+                };
+                let Some(candidate) = add_candidate(&mut vec, sysroot, home) else {
+                    continue;
+                };
+                // Aliases are homes, record the entry's home if it is a link, such as
+                // `/usr/lib/jvm/default-java/bin`, but never the executable itself.
+                if let Some(entry_home) = dir.parent().filter(|_| dir.ends_with("bin")) {
+                    if sysroot.canonicalize(entry_home).is_ok_and(|e| e == candidate.path) {
+                        candidate.with_alias(entry_home);
+                    }
+                }
+            }
+        }
+        Some(vec)
+    }
+}
+
+/// Resolves the home directory of the JVM a java executable belongs to.
+///
+/// Symlinks are followed, so alternatives systems such as
+/// `/usr/bin/java -> /etc/alternatives/java -> /usr/lib/jvm/...` resolve
+/// to the real home directory.
+///
+/// * `sysroot` - The system being searched, to resolve symlinks within.
+/// * `executable` - The java executable.
+///
+/// # Returns
+/// Some containing the home directory, otherwise None.
+pub(crate) fn resolve_home(sysroot: &Sysroot, executable: &Path) -> Option<PathBuf> {
+    let executable = sysroot.canonicalize(executable).ok()?;
+    let bin_dir = executable.parent()?;
+    if !bin_dir.file_name()?.eq("bin") {
+        return None;
+    }
+
+    let home = bin_dir.parent()?;
+    // The macOS /usr/bin/java is a stub which prompts to install a JVM when run.
+    if cfg!(target_os = "macos") && home.eq(&sysroot.path("/usr")) {
+        return None;
+    }
+
+    // Java 8 and older JDKs include a nested jre, we want the JDK.
+    if home.file_name()?.eq("jre") && sysroot.exists(JavaInstall::get_java_executable(home.parent()?, true)) {
+        return Some(home.parent()?.to_path_buf());
+    }
+    Some(home.to_path_buf())
+}
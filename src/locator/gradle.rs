@@ -1,24 +1,260 @@
-use crate::install::JavaInstall;
-use crate::locator::{scan_folder, JavaLocator};
+use crate::locator::sysroot::Sysroot;
+use crate::locator::{add_candidate, scan_folder, JavaCandidate, JavaLocator};
 use crate::log_debug;
+use std::collections::HashMap;
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// The metadata key for the environment variable an install was found through,
+/// listed in `org.gradle.java.installations.fromEnv`.
+pub const GRADLE_ENV_VAR: &str = "gradle.env_var";
+
+/// The property listing extra installation paths.
+const INSTALLATION_PATHS: &str = "org.gradle.java.installations.paths";
+/// The property listing environment variables pointing to installations.
+const INSTALLATIONS_FROM_ENV: &str = "org.gradle.java.installations.fromEnv";
+
+/// The files marking the root of a Gradle build.
+const SETTINGS_FILES: &[&str] = &["settings.gradle", "settings.gradle.kts"];
 
 /// A JavaLocator capable of locating JVM's installed by Gradle toolchains.
+///
+/// This also finds installations configured with the `org.gradle.java.installations.paths`
+/// and `org.gradle.java.installations.fromEnv` properties, in the user's and the
+/// project's `gradle.properties`. The user's file takes precedence, as in Gradle.
 #[derive(Default)]
-pub struct GradleJavaLocator {}
+pub struct GradleJavaLocator {
+    project_dir: Option<PathBuf>,
+}
 
 impl GradleJavaLocator {
     pub fn new() -> Self {
         Default::default()
     }
+
+    /// Create a locator which reads the `gradle.properties` of the build containing
+    /// the given directory, instead of the current directory.
+    ///
+    /// * `dir` - A directory within the Gradle build. Within a sysroot, this is
+    ///   re-rooted under it.
+    pub fn with_project_dir(dir: impl AsRef<Path>) -> Self {
+        Self {
+            project_dir: Some(dir.as_ref().to_path_buf()),
+        }
+    }
+
+    /// Gets the Gradle user homes, from `GRADLE_USER_HOME` or `~/.gradle`.
+    fn user_homes(sysroot: &Sysroot) -> Vec<PathBuf> {
+        sysroot.dirs_from_env_or_home("GRADLE_USER_HOME", ".gradle")
+    }
+
+    /// Gets the root directory of the build, the nearest directory with a settings
+    /// file, otherwise the project directory itself.
+    ///
+    /// Within a sysroot, the current directory is never used.
+    fn root_project_dir(&self, sysroot: &Sysroot) -> Option<PathBuf> {
+        let dir = match &self.project_dir {
+            Some(dir) => sysroot.path(dir),
+            None if sysroot.is_host() => env::current_dir().ok()?,
+            None => return None,
+        };
+        let root = dir.ancestors()
+            .find(|e| SETTINGS_FILES.iter().any(|f| sysroot.is_file(e.join(f))))
+            .map(Path::to_path_buf);
+        Some(root.unwrap_or(dir))
+    }
 }
 
 impl JavaLocator for GradleJavaLocator {
-    fn locate(&self) -> Option<Vec<JavaInstall>> {
-        let dir = dirs::home_dir()?.join(".gradle/jdks");
-        log_debug!("Searching for JVM's installed by Gradle toolchains in path: {:?}", &dir);
+    fn name(&self) -> &'static str {
+        "Gradle"
+    }
 
-        let mut vec: Vec<JavaInstall> = Vec::new();
-        scan_folder(&mut vec, &dir);
+    fn find_candidates(&self, sysroot: &Sysroot) -> Option<Vec<JavaCandidate>> {
+        let project_properties = self.root_project_dir(sysroot)
+            .map(|e| read_properties(sysroot, &e.join("gradle.properties")))
+            .unwrap_or_default();
+
+        let mut vec: Vec<JavaCandidate> = Vec::new();
+        let user_homes = Self::user_homes(sysroot);
+        if user_homes.is_empty() {
+            add_installations(&mut vec, sysroot, &project_properties);
+        }
+        for user_home in user_homes {
+            let dir = user_home.join("jdks");
+            log_debug!("Searching for JVM's installed by Gradle toolchains in path: {:?}", &dir);
+            scan_folder(&mut vec, sysroot, &dir);
+
+            let mut properties = project_properties.clone();
+            properties.extend(read_properties(sysroot, &user_home.join("gradle.properties")));
+            add_installations(&mut vec, sysroot, &properties);
+        }
         Some(vec)
     }
 }
+
+/// Adds the installations configured by the given Gradle properties.
+fn add_installations(vec: &mut Vec<JavaCandidate>, sysroot: &Sysroot, properties: &HashMap<String, String>) {
+    if let Some(paths) = properties.get(INSTALLATION_PATHS) {
+        log_debug!("Searching for JVM's listed in Gradle installation paths: {}", paths);
+        for path in list_property(paths) {
+            add_candidate(vec, sysroot, sysroot.path(path));
+        }
+    }
+    if let Some(vars) = properties.get(INSTALLATIONS_FROM_ENV) {
+        log_debug!("Searching for JVM's in Gradle installation environment variables: {}", vars);
+        for var in list_property(vars) {
+            let Some(path) = sysroot.var_os(var) else {
+                continue;
+            };
+            if let Some(candidate) = add_candidate(vec, sysroot, path) {
+                candidate.metadata.entry(GRADLE_ENV_VAR.into()).or_insert_with(|| var.into());
+            }
+        }
+    }
+}
+
+/// Splits a comma separated property value, ignoring empty entries.
+fn list_property(value: &str) -> impl Iterator<Item = &str> {
+    value.split(',').map(str::trim).filter(|e| !e.is_empty())
+}
+
+/// Reads a Java properties file, as used for `gradle.properties`.
+///
+/// Supports `=`, `:` and whitespace separators, `#` and `!` comments, line
+/// continuations and escape sequences.
+///
+/// # Returns
+/// The properties, empty if the file can't be read.
+fn read_properties(sysroot: &Sysroot, file: &Path) -> HashMap<String, String> {
+    let Ok(contents) = sysroot.read_to_string(file) else {
+        return HashMap::new();
+    };
+
+    let mut properties: HashMap<String, String> = HashMap::new();
+    let mut lines = contents.lines();
+    while let Some(line) = lines.next() {
+        let mut line = line.trim_start().to_string();
+        if line.is_empty() || line.starts_with(['#', '!']) {
+            continue;
+        }
+        // An odd number of trailing backslashes continues the line.
+        while line.chars().rev().take_while(|e| *e == '\\').count() % 2 == 1 {
+            line.pop();
+            match lines.next() {
+                Some(next) => line.push_str(next.trim_start()),
+                None => break,
+            }
+        }
+
+        let (key, value) = split_property(&line);
+        properties.insert(unescape(key), unescape(value));
+    }
+    properties
+}
+
+/// Splits a logical property line at the first unescaped separator.
+fn split_property(line: &str) -> (&str, &str) {
+    let mut escaped = false;
+    for (index, c) in line.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == '=' || c == ':' || c.is_whitespace() {
+            let value = if c.is_whitespace() {
+                // Whitespace may surround an explicit separator.
+                let rest = line[index..].trim_start();
+                rest.strip_prefix(['=', ':']).unwrap_or(rest)
+            } else {
+                &line[index + 1..]
+            };
+            return (&line[..index], value.trim_start());
+        }
+    }
+    (line, "")
+}
+
+/// Resolves the escape sequences in a property key or value.
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => unescaped.push('\t'),
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some('f') => unescaped.push('\u{c}'),
+            Some('u') => {
+                let hex: String = chars.by_ref().take(4).collect();
+                if let Some(c) = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                    unescaped.push(c);
+                }
+            }
+            Some(c) => unescaped.push(c),
+            None => {}
+        }
+    }
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn parse(contents: &str) -> HashMap<String, String> {
+        let temp = TempDir::new().unwrap();
+        let file = temp.path().join("gradle.properties");
+        fs::write(&file, contents).unwrap();
+        read_properties(&Sysroot::host(), &file)
+    }
+
+    #[test]
+    fn splits_at_separators() {
+        assert_eq!(split_property("a=b"), ("a", "b"));
+        assert_eq!(split_property("a:b"), ("a", "b"));
+        assert_eq!(split_property("a b"), ("a", "b"));
+        assert_eq!(split_property("a  =  b = c"), ("a", "b = c"));
+        assert_eq!(split_property("a\\=b=c"), ("a\\=b", "c"));
+        assert_eq!(split_property("a"), ("a", ""));
+    }
+
+    #[test]
+    fn unescapes() {
+        assert_eq!(unescape("C\\:\\\\Program Files\\\\Java"), "C:\\Program Files\\Java");
+        assert_eq!(unescape("a\\tb\\nc"), "a\tb\nc");
+        assert_eq!(unescape("\\u00e9t\\u00E9"), "été");
+        assert_eq!(unescape("trailing\\"), "trailing");
+    }
+
+    #[test]
+    fn reads_properties() {
+        let properties = parse(concat!(
+            "# comment\n",
+            "! also a comment\n",
+            "\n",
+            "   org.gradle.java.home = /opt/jdk\n",
+            "org.gradle.java.installations.paths=/a,\\\n",
+            "    /b,\\\n",
+            "    /c\n",
+            "escaped=ends with a backslash\\\\\n",
+            "next:value\n",
+        ));
+        assert_eq!(properties["org.gradle.java.home"], "/opt/jdk");
+        assert_eq!(properties["org.gradle.java.installations.paths"], "/a,/b,/c");
+        assert_eq!(properties["escaped"], "ends with a backslash\\");
+        assert_eq!(properties["next"], "value");
+        assert_eq!(properties.len(), 4);
+    }
+
+    #[test]
+    fn continues_at_end_of_file() {
+        assert_eq!(parse("key=value\\")["key"], "value");
+    }
+}
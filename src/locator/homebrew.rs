@@ -0,0 +1,181 @@
+use crate::locator::sysroot::Sysroot;
+use crate::locator::{add_candidate, is_java_home, list_dir, JavaCandidate, JavaLocator};
+use std::path::{Path, PathBuf};
+
+/// The metadata key for the Homebrew formula an install belongs to, such as `openjdk@17`.
+pub const HOMEBREW_FORMULA: &str = "homebrew.formula";
+/// The metadata key for the Homebrew keg version of an install, such as `17.0.10`.
+pub const HOMEBREW_VERSION: &str = "homebrew.version";
+
+/// The default Homebrew prefixes on macOS, for Apple Silicon and Intel respectively.
+const MACOS_PREFIXES: &[&str] = &["/opt/homebrew", "/usr/local"];
+
+/// The default system wide Linuxbrew prefix.
+const LINUX_PREFIX: &str = "/home/linuxbrew/.linuxbrew";
+
+/// A JavaLocator capable of locating JVM's installed as Homebrew or Linuxbrew
+/// `openjdk` formulae.
+///
+/// Formulae are keg-only, the JVM lives in the keg's `libexec` directory. On macOS
+/// this is a bundle, `libexec/openjdk.jdk/Contents/Home`, both layouts are understood
+/// on every platform.
+#[derive(Default)]
+pub struct HomebrewJavaLocator {
+    prefixes: Option<Vec<PathBuf>>,
+}
+
+impl HomebrewJavaLocator {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Create a locator which searches the given Homebrew prefix, instead of
+    /// `HOMEBREW_PREFIX` or the platform defaults.
+    ///
+    /// * `prefix` - The Homebrew prefix, containing the `Cellar` and `opt` directories.
+    ///   Within a sysroot, this is re-rooted under it.
+    pub fn with_prefix(prefix: impl AsRef<Path>) -> Self {
+        Self {
+            prefixes: Some(vec![prefix.as_ref().to_path_buf()]),
+        }
+    }
+
+    fn prefixes(&self, sysroot: &Sysroot) -> Vec<PathBuf> {
+        if let Some(prefixes) = &self.prefixes {
+            return prefixes.iter().map(|e| sysroot.path(e)).collect();
+        }
+        if let Some(prefix) = sysroot.var_os("HOMEBREW_PREFIX") {
+            return vec![PathBuf::from(prefix)];
+        }
+
+        if cfg!(target_os = "macos") {
+            MACOS_PREFIXES.iter().map(|e| sysroot.path(e)).collect()
+        } else {
+            let mut prefixes = vec![sysroot.path(LINUX_PREFIX)];
+            prefixes.extend(sysroot.users().into_iter().map(|e| e.home.join(".linuxbrew")));
+            prefixes
+        }
+    }
+}
+
+impl JavaLocator for HomebrewJavaLocator {
+    fn name(&self) -> &'static str {
+        "Homebrew"
+    }
+
+    fn find_candidates(&self, sysroot: &Sysroot) -> Option<Vec<JavaCandidate>> {
+        let mut vec: Vec<JavaCandidate> = Vec::new();
+        for prefix in self.prefixes(sysroot) {
+            log_debug!("Searching for JVM's installed by Homebrew in prefix: {:?}", &prefix);
+
+            // Every installed version of each formula.
+            for formula in openjdk_entries(sysroot, &prefix.join("Cellar")) {
+                for keg in list_dir(sysroot, &formula) {
+                    add_keg(&mut vec, sysroot, &keg.path());
+                }
+            }
+
+            // The linked version of each formula, these point into the Cellar but the
+            // Cellar may be elsewhere.
+            for opt in openjdk_entries(sysroot, &prefix.join("opt")) {
+                add_keg(&mut vec, sysroot, &opt);
+            }
+        }
+        Some(vec)
+    }
+}
+
+/// Lists the entries of the given directory which belong to `openjdk` formulae,
+/// such as `openjdk` and `openjdk@17`.
+fn openjdk_entries(sysroot: &Sysroot, dir: &Path) -> Vec<PathBuf> {
+    list_dir(sysroot, dir).into_iter()
+        .map(|e| e.path())
+        .filter(|e| e.file_name().and_then(|e| e.to_str()).is_some_and(|e| e == "openjdk" || e.starts_with("openjdk@")))
+        .collect()
+}
+
+/// Adds the JVM within a keg, `Cellar/<formula>/<version>`.
+///
+/// `opt` links collapse into their Cellar kegs, the formula and version are read from
+/// the canonical keg.
+fn add_keg(vec: &mut Vec<JavaCandidate>, sysroot: &Sysroot, keg: &Path) {
+    let libexec = keg.join("libexec");
+    let Some(home) = [libexec.join("openjdk.jdk/Contents/Home"), libexec].into_iter().find(|e| is_java_home(sysroot, e)) else {
+        return;
+    };
+
+    let Ok(keg) = sysroot.canonicalize(keg) else {
+        return;
+    };
+    if let Some(candidate) = add_candidate(vec, sysroot, &home) {
+        if let Some(version) = keg.file_name() {
+            candidate.with_metadata(HOMEBREW_VERSION, version.to_string_lossy());
+        }
+        if let Some(formula) = keg.parent().and_then(|e| e.file_name()) {
+            candidate.with_metadata(HOMEBREW_FORMULA, formula.to_string_lossy());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::install::JavaInstall;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const PREFIX: &str = "/home/linuxbrew/.linuxbrew";
+
+    fn create_home(home: &Path) {
+        fs::create_dir_all(home.join("bin")).unwrap();
+        fs::write(JavaInstall::get_java_executable(home, true), "").unwrap();
+    }
+
+    fn find(root: &Path) -> Vec<JavaCandidate> {
+        HomebrewJavaLocator::with_prefix(PREFIX).find_candidates(&Sysroot::new(root)).unwrap()
+    }
+
+    #[test]
+    fn finds_linux_kegs() {
+        let temp = TempDir::new().unwrap();
+        let root = fs::canonicalize(temp.path()).unwrap();
+        let keg = root.join("home/linuxbrew/.linuxbrew/Cellar/openjdk@17/17.0.10");
+        create_home(&keg.join("libexec"));
+
+        let candidates = find(&root);
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].path, keg.join("libexec"));
+        assert_eq!(candidates[0].metadata[HOMEBREW_FORMULA], "openjdk@17");
+        assert_eq!(candidates[0].metadata[HOMEBREW_VERSION], "17.0.10");
+    }
+
+    #[test]
+    fn finds_bundle_kegs() {
+        let temp = TempDir::new().unwrap();
+        let root = fs::canonicalize(temp.path()).unwrap();
+        let home = root.join("home/linuxbrew/.linuxbrew/Cellar/openjdk/21.0.2/libexec/openjdk.jdk/Contents/Home");
+        create_home(&home);
+
+        let candidates = find(&root);
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].path, home);
+        assert_eq!(candidates[0].metadata[HOMEBREW_FORMULA], "openjdk");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn resolves_opt_links_within_sysroot() {
+        let temp = TempDir::new().unwrap();
+        let root = fs::canonicalize(temp.path()).unwrap();
+        let keg = root.join("home/linuxbrew/.linuxbrew/Cellar/openjdk@17/17.0.10");
+        create_home(&keg.join("libexec"));
+        let opt = root.join("home/linuxbrew/.linuxbrew/opt");
+        fs::create_dir_all(&opt).unwrap();
+        std::os::unix::fs::symlink(format!("{}/Cellar/openjdk@17/17.0.10", PREFIX), opt.join("openjdk@17")).unwrap();
+
+        let candidates = find(&root);
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].path, keg.join("libexec"));
+        assert_eq!(candidates[0].aliases, [opt.join("openjdk@17/libexec")]);
+    }
+}
@@ -1,8 +1,19 @@
-use crate::install::JavaInstall;
-use crate::locator::{scan_folder, JavaLocator};
+use crate::locator::sysroot::{Sysroot, UserDirs};
+use crate::locator::{add_candidate, list_dir, scan_folder, JavaCandidate, JavaLocator};
 use crate::log_debug;
+use std::path::{Path, PathBuf};
+
+/// The metadata key for the name a user gave a JDK in IntelliJ, such as `corretto-17`.
+pub const INTELLIJ_JDK_NAME: &str = "intellij.jdk_name";
+
+/// The vendor directories containing per-product config directories, such as
+/// `JetBrains/IntelliJIdea2024.1` and `Google/AndroidStudio2024.1`.
+const CONFIG_VENDORS: &[&str] = &["JetBrains", "Google"];
 
 /// A JavaLocator capable of locating JVM's installed by Intellij IDEA.
+///
+/// This also finds every JDK registered in the `jdk.table.xml` of any installed
+/// IntelliJ based product, which may be anywhere on the system.
 #[derive(Default)]
 pub struct IntelliJJavaLocator {}
 
@@ -10,15 +21,77 @@ impl IntelliJJavaLocator {
     pub fn new() -> Self {
         Default::default()
     }
+
+    fn jdk_tables(sysroot: &Sysroot, user: &UserDirs) -> Vec<PathBuf> {
+        CONFIG_VENDORS.iter()
+            .flat_map(|e| list_dir(sysroot, user.config.join(e)))
+            .map(|e| e.path().join("options/jdk.table.xml"))
+            .filter(|e| sysroot.is_file(e))
+            .collect()
+    }
 }
 
 impl JavaLocator for IntelliJJavaLocator {
-    fn locate(&self) -> Option<Vec<JavaInstall>> {
-        let dir = dirs::home_dir()?.join(".jdks");
-        log_debug!("Searching for JVM's installed by Intellij toolchains in path: {:?}", &dir);
+    fn name(&self) -> &'static str {
+        "IntelliJ IDEA"
+    }
 
-        let mut vec: Vec<JavaInstall> = Vec::new();
-        scan_folder(&mut vec, &dir);
+    fn find_candidates(&self, sysroot: &Sysroot) -> Option<Vec<JavaCandidate>> {
+        let mut vec: Vec<JavaCandidate> = Vec::new();
+        for user in sysroot.users() {
+            let dir = user.home.join(".jdks");
+            log_debug!("Searching for JVM's installed by Intellij toolchains in path: {:?}", &dir);
+            scan_folder(&mut vec, sysroot, &dir);
+
+            // Registered paths are on the searched system, `$USER_HOME$` is expanded to
+            // the target path and the result re-rooted.
+            let Some(target_home) = sysroot.target_path(&user.home) else {
+                continue;
+            };
+            for table in Self::jdk_tables(sysroot, &user) {
+                log_debug!("Searching for JVM's registered in Intellij JDK table: {:?}", &table);
+                for (name, path) in read_jdk_table(sysroot, &table, &target_home) {
+                    if let Some(candidate) = add_candidate(&mut vec, sysroot, sysroot.path(&path)) {
+                        candidate.metadata.entry(INTELLIJ_JDK_NAME.into()).or_insert(name);
+                    }
+                }
+            }
+        }
         Some(vec)
     }
 }
+
+/// Reads the Java SDK's registered in a `jdk.table.xml` file.
+///
+/// Entries using path macros other than `$USER_HOME$` are skipped, as they are
+/// relative to an IDE install we don't know about.
+///
+/// # Returns
+/// The name and home path of each Java SDK.
+fn read_jdk_table(sysroot: &Sysroot, table: &Path, user_home: &Path) -> Vec<(String, PathBuf)> {
+    let Ok(contents) = sysroot.read_to_string(table) else {
+        return Vec::new();
+    };
+    let Ok(document) = roxmltree::Document::parse(&contents) else {
+        log_debug!("Failed to parse Intellij JDK table: {:?}", table);
+        return Vec::new();
+    };
+
+    let value = |jdk: roxmltree::Node, tag: &str| jdk.children()
+        .find(|e| e.has_tag_name(tag))
+        .and_then(|e| e.attribute("value"))
+        .map(str::to_owned);
+
+    document.descendants()
+        .filter(|e| e.has_tag_name("jdk"))
+        .filter(|e| value(*e, "type").is_some_and(|t| t == "JavaSDK"))
+        .filter_map(|e| {
+            let name = value(e, "name")?;
+            let path = value(e, "homePath")?.replace("$USER_HOME$", &user_home.to_string_lossy());
+            if path.contains('$') {
+                return None;
+            }
+            Some((name, PathBuf::from(path)))
+        })
+        .collect()
+}
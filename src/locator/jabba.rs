@@ -0,0 +1,39 @@
+use crate::locator::sysroot::Sysroot;
+use crate::locator::{scan_aliased_folder, JavaCandidate, JavaLocator};
+use std::path::PathBuf;
+
+/// The metadata key for the jabba version name of an install, such as `zulu@1.17.0`.
+pub const JABBA_VERSION: &str = "jabba.version";
+
+/// A JavaLocator capable of locating JVM's installed by jabba.
+///
+/// Versions linked with `jabba link` are symlinks, these are resolved to their
+/// canonical homes.
+#[derive(Default)]
+pub struct JabbaJavaLocator {}
+
+impl JabbaJavaLocator {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    fn jabba_homes(sysroot: &Sysroot) -> Vec<PathBuf> {
+        sysroot.dirs_from_env_or_home("JABBA_HOME", ".jabba")
+    }
+}
+
+impl JavaLocator for JabbaJavaLocator {
+    fn name(&self) -> &'static str {
+        "Jabba"
+    }
+
+    fn find_candidates(&self, sysroot: &Sysroot) -> Option<Vec<JavaCandidate>> {
+        let mut vec: Vec<JavaCandidate> = Vec::new();
+        for dir in Self::jabba_homes(sysroot) {
+            let dir = dir.join("jdk");
+            log_debug!("Searching for JVM's installed by jabba in path: {:?}", &dir);
+            scan_aliased_folder(&mut vec, sysroot, &dir, JABBA_VERSION);
+        }
+        Some(vec)
+    }
+}
@@ -0,0 +1,151 @@
+use crate::locator::sysroot::Sysroot;
+use crate::locator::{add_candidate, is_java_home, list_dir, JavaCandidate, JavaLocator};
+use std::path::{Path, PathBuf};
+
+/// The metadata key for the name of the IDE which bundles an install, such as `IntelliJ IDEA`.
+pub const JETBRAINS_PRODUCT: &str = "jetbrains.product";
+/// The metadata key for the build of the IDE which bundles an install, such as `IU-241.14494.240`.
+pub const JETBRAINS_BUILD: &str = "jetbrains.build";
+
+/// How deep to search for IDE installs. Toolbox 1.x installs are nested
+/// at `apps/<app>/<channel>/<build>`.
+const MAX_DEPTH: usize = 3;
+
+/// The locations of the bundled runtime, relative to the IDE install.
+const JBR_PATHS: &[&str] = &[
+    "jbr",
+    "jbr/Contents/Home",
+    "Contents/jbr/Contents/Home",
+    // Older Android Studio releases.
+    "jre",
+    "Contents/jre/Contents/Home",
+];
+
+/// Standalone IDE install locations, searched in addition to JetBrains Toolbox.
+#[cfg(target_os = "linux")]
+const INSTALL_DIRS: &[&str] = &["/opt", "/usr/local", "/snap"];
+#[cfg(target_os = "macos")]
+const INSTALL_DIRS: &[&str] = &["/Applications"];
+#[cfg(target_os = "windows")]
+const INSTALL_DIRS: &[&str] = &["C:/Program Files/JetBrains", "C:/Program Files/Android"];
+
+/// A JavaLocator capable of locating the JetBrains Runtime bundled with JetBrains
+/// IDE's, such as IntelliJ IDEA and Android Studio.
+///
+/// Both JetBrains Toolbox and standalone IDE installs are searched.
+#[derive(Default)]
+pub struct JetBrainsRuntimeJavaLocator {}
+
+impl JetBrainsRuntimeJavaLocator {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    fn search_dirs(sysroot: &Sysroot) -> Vec<PathBuf> {
+        let mut dirs: Vec<PathBuf> = Vec::new();
+        for user in sysroot.users() {
+            dirs.push(user.data_local.join("JetBrains/Toolbox/apps"));
+            // Toolbox 2.x installs apps here on macOS.
+            dirs.push(user.home.join("Applications"));
+        }
+        dirs.extend(INSTALL_DIRS.iter().map(|e| sysroot.path(e)));
+        dirs
+    }
+}
+
+impl JavaLocator for JetBrainsRuntimeJavaLocator {
+    fn name(&self) -> &'static str {
+        "JetBrains Runtime"
+    }
+
+    fn find_candidates(&self, sysroot: &Sysroot) -> Option<Vec<JavaCandidate>> {
+        let mut vec: Vec<JavaCandidate> = Vec::new();
+        for dir in Self::search_dirs(sysroot) {
+            log_debug!("Searching for JVM's bundled with JetBrains IDE's in path: {:?}", &dir);
+            scan_ide_installs(&mut vec, sysroot, &dir, 0);
+        }
+        Some(vec)
+    }
+}
+
+fn scan_ide_installs(vec: &mut Vec<JavaCandidate>, sysroot: &Sysroot, dir: &Path, depth: usize) {
+    if depth >= MAX_DEPTH {
+        return;
+    }
+
+    for entry in list_dir(sysroot, dir) {
+        let path = entry.path();
+        if !sysroot.is_dir(&path) {
+            continue;
+        }
+
+        let Some(product) = ProductInfo::read(sysroot, &path) else {
+            scan_ide_installs(vec, sysroot, &path, depth + 1);
+            continue;
+        };
+
+        let Some(jbr) = JBR_PATHS.iter().map(|e| path.join(e)).find(|e| is_java_home(sysroot, e)) else {
+            continue;
+        };
+        // Snaps link `current` to a revision, these collapse into the revision.
+        if let Some(candidate) = add_candidate(vec, sysroot, jbr) {
+            candidate.with_metadata(JETBRAINS_PRODUCT, product.name);
+            candidate.with_metadata(JETBRAINS_BUILD, product.build);
+        }
+    }
+}
+
+/// The product name and build of an IDE install.
+struct ProductInfo {
+    name: String,
+    build: String,
+}
+
+impl ProductInfo {
+    /// Reads the product information of an IDE install, from its `build.txt` file
+    /// and, if available, its `product-info.json` file.
+    ///
+    /// # Returns
+    /// Some containing the product information, otherwise None if this is not an IDE install.
+    fn read(sysroot: &Sysroot, ide_dir: &Path) -> Option<Self> {
+        // macOS app bundles keep these in the Resources directory.
+        let resources = [ide_dir.to_path_buf(), ide_dir.join("Contents/Resources")].into_iter()
+            .find(|e| sysroot.is_file(e.join("build.txt")))?;
+
+        // Builds are prefixed with the product code, `IU-241.14494.240`.
+        let build = sysroot.read_to_string(resources.join("build.txt")).ok()?.trim().to_string();
+        let name = sysroot.read_to_string(resources.join("product-info.json")).ok()
+            .and_then(|e| json_string(&e, "name"))
+            .or_else(|| Some(build.split_once('-')?.0.to_string()))
+            .unwrap_or_default();
+        Some(Self { name, build })
+    }
+}
+
+/// Extracts the first string value with the given key from a JSON document.
+///
+/// Top level keys of `product-info.json` come before any nested objects, so this
+/// avoids requiring a full JSON parser.
+fn json_string(json: &str, key: &str) -> Option<String> {
+    let quoted = format!("\"{}\"", key);
+    json.match_indices(&quoted).find_map(|(index, _)| {
+        let value = json[index + quoted.len()..].trim_start()
+            .strip_prefix(':')?
+            .trim_start()
+            .strip_prefix('"')?;
+        Some(value[..value.find('"')?].to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_json_strings() {
+        let json = r#"{ "productCode" : "name", "name": "IntelliJ IDEA", "launch": [{ "name": "nested" }] }"#;
+        assert_eq!(json_string(json, "name").as_deref(), Some("IntelliJ IDEA"));
+        assert_eq!(json_string(json, "productCode").as_deref(), Some("name"));
+        assert_eq!(json_string(json, "version"), None);
+    }
+}
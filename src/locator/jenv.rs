@@ -0,0 +1,39 @@
+use crate::locator::sysroot::Sysroot;
+use crate::locator::{scan_aliased_folder, JavaCandidate, JavaLocator};
+use std::path::PathBuf;
+
+/// The metadata key for the jenv version name of an install, such as `openjdk64-17.0.10`.
+pub const JENV_VERSION: &str = "jenv.version";
+
+/// A JavaLocator capable of locating JVM's registered with jenv.
+///
+/// jenv versions are usually symlinks to homes elsewhere on the system, these
+/// are resolved to their canonical homes.
+#[derive(Default)]
+pub struct JenvJavaLocator {}
+
+impl JenvJavaLocator {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    fn jenv_roots(sysroot: &Sysroot) -> Vec<PathBuf> {
+        sysroot.dirs_from_env_or_home("JENV_ROOT", ".jenv")
+    }
+}
+
+impl JavaLocator for JenvJavaLocator {
+    fn name(&self) -> &'static str {
+        "jEnv"
+    }
+
+    fn find_candidates(&self, sysroot: &Sysroot) -> Option<Vec<JavaCandidate>> {
+        let mut vec: Vec<JavaCandidate> = Vec::new();
+        for dir in Self::jenv_roots(sysroot) {
+            let dir = dir.join("versions");
+            log_debug!("Searching for JVM's registered with jenv in path: {:?}", &dir);
+            scan_aliased_folder(&mut vec, sysroot, &dir, JENV_VERSION);
+        }
+        Some(vec)
+    }
+}
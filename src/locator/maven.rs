@@ -0,0 +1,200 @@
+use crate::install::{JavaInstall, JavaVersion};
+use crate::locator::sysroot::Sysroot;
+use crate::locator::{add_candidate, JavaCandidate, JavaLocator};
+use std::path::{Path, PathBuf};
+
+/// The metadata key for the `toolchains.xml` file which declares an install.
+pub const MAVEN_TOOLCHAINS_FILE: &str = "maven.toolchains_file";
+/// The metadata key for the version a Maven toolchain declares it provides, such as `17`.
+pub const MAVEN_TOOLCHAIN_VERSION: &str = "maven.toolchain.version";
+/// The metadata key for the vendor a Maven toolchain declares it provides, such as `temurin`.
+pub const MAVEN_TOOLCHAIN_VENDOR: &str = "maven.toolchain.vendor";
+/// The metadata key for the id a Maven toolchain declares it provides.
+pub const MAVEN_TOOLCHAIN_ID: &str = "maven.toolchain.id";
+
+/// A JavaLocator capable of locating JDK's declared in Maven `toolchains.xml` files.
+///
+/// The user's `~/.m2/toolchains.xml` and the global `$MAVEN_HOME/conf/toolchains.xml`
+/// are read. Each located install is checked against the version and vendor its
+/// toolchain declares, logging a warning on mismatch.
+#[derive(Default)]
+pub struct MavenToolchainsJavaLocator {
+    files: Option<Vec<PathBuf>>,
+}
+
+impl MavenToolchainsJavaLocator {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Create a locator which reads the given `toolchains.xml` file, instead of
+    /// the user and global files.
+    ///
+    /// * `file` - The path of the `toolchains.xml` file. Within a sysroot, this is
+    ///   re-rooted under it.
+    pub fn with_file(file: impl AsRef<Path>) -> Self {
+        Self {
+            files: Some(vec![file.as_ref().to_path_buf()]),
+        }
+    }
+
+    /// Gets the `toolchains.xml` files to read, along with the path of the home of
+    /// the user they belong to on the searched system, for expanding `${user.home}`.
+    fn files(&self, sysroot: &Sysroot) -> Vec<(PathBuf, Option<PathBuf>)> {
+        let users = sysroot.users();
+        // The global file is expanded for the current user, if there is one.
+        let current_user = || users.first().filter(|_| sysroot.is_host()).and_then(|e| sysroot.target_path(&e.home));
+        if let Some(files) = &self.files {
+            return files.iter().map(|e| (sysroot.path(e), current_user())).collect();
+        }
+
+        let mut files: Vec<(PathBuf, Option<PathBuf>)> = users.iter()
+            .map(|e| (e.home.join(".m2/toolchains.xml"), sysroot.target_path(&e.home)))
+            .collect();
+        if let Some(maven_home) = ["MAVEN_HOME", "M2_HOME"].into_iter().find_map(|e| sysroot.var_os(e)) {
+            files.push((PathBuf::from(maven_home).join("conf/toolchains.xml"), current_user()));
+        }
+        files
+    }
+}
+
+impl JavaLocator for MavenToolchainsJavaLocator {
+    fn name(&self) -> &'static str {
+        "Maven toolchains"
+    }
+
+    fn find_candidates(&self, sysroot: &Sysroot) -> Option<Vec<JavaCandidate>> {
+        let mut vec: Vec<JavaCandidate> = Vec::new();
+        for (file, user_home) in self.files(sysroot) {
+            log_debug!("Searching for JVM's declared in Maven toolchains: {:?}", &file);
+            for toolchain in read_toolchains(sysroot, &file, user_home.as_deref()) {
+                let Some(candidate) = add_candidate(&mut vec, sysroot, sysroot.path(&toolchain.jdk_home)) else {
+                    log_debug!("Maven toolchain jdkHome {:?} is not a Java installation.", &toolchain.jdk_home);
+                    continue;
+                };
+                // The user's file is read first, and takes precedence.
+                let mut declare = |key: &str, value: Option<String>| {
+                    if let Some(value) = value {
+                        candidate.metadata.entry(key.into()).or_insert(value);
+                    }
+                };
+                declare(MAVEN_TOOLCHAINS_FILE, sysroot.target_path(&file).map(|e| e.to_string_lossy().into_owned()));
+                declare(MAVEN_TOOLCHAIN_VERSION, toolchain.version);
+                declare(MAVEN_TOOLCHAIN_VENDOR, toolchain.vendor);
+                declare(MAVEN_TOOLCHAIN_ID, toolchain.id);
+            }
+        }
+        Some(vec)
+    }
+
+    fn verify(&self, install: &JavaInstall) {
+        let Some(file) = install.metadata.get(MAVEN_TOOLCHAINS_FILE) else {
+            return;
+        };
+
+        if let Some(version) = install.metadata.get(MAVEN_TOOLCHAIN_VERSION) {
+            if !version_matches(version, install) {
+                log_warn!("Maven toolchain in {} declares version {} for {:?}, but found {}.",
+                    file, version, &install.java_home, install.lang_version.short_string());
+            }
+        }
+        if let Some(vendor) = install.metadata.get(MAVEN_TOOLCHAIN_VENDOR) {
+            if !vendor_matches(vendor, install) {
+                log_warn!("Maven toolchain in {} declares vendor {} for {:?}, but found {}.",
+                    file, vendor, &install.java_home, &install.vendor);
+            }
+        }
+    }
+}
+
+/// A `jdk` toolchain declared in a `toolchains.xml` file.
+struct Toolchain {
+    jdk_home: PathBuf,
+    version: Option<String>,
+    vendor: Option<String>,
+    id: Option<String>,
+}
+
+/// Reads the `jdk` toolchains declared in a `toolchains.xml` file.
+///
+/// `${user.home}` and `${env.NAME}` expressions in `jdkHome` are expanded, toolchains
+/// using any other expression are skipped.
+fn read_toolchains(sysroot: &Sysroot, file: &Path, user_home: Option<&Path>) -> Vec<Toolchain> {
+    let Ok(contents) = sysroot.read_to_string(file) else {
+        return Vec::new();
+    };
+    let Ok(document) = roxmltree::Document::parse(&contents) else {
+        log_debug!("Failed to parse Maven toolchains: {:?}", file);
+        return Vec::new();
+    };
+
+    let text = |node: Option<roxmltree::Node>, tag: &str| node?.children()
+        .find(|e| e.has_tag_name(tag))
+        .and_then(|e| e.text())
+        .map(|e| e.trim().to_string())
+        .filter(|e| !e.is_empty());
+
+    document.descendants()
+        .filter(|e| e.has_tag_name("toolchain"))
+        .filter(|e| text(Some(*e), "type").is_some_and(|t| t == "jdk"))
+        .filter_map(|e| {
+            let provides = e.children().find(|e| e.has_tag_name("provides"));
+            let configuration = e.children().find(|e| e.has_tag_name("configuration"));
+            Some(Toolchain {
+                jdk_home: PathBuf::from(expand(sysroot, &text(configuration, "jdkHome")?, user_home)?),
+                version: text(provides, "version"),
+                vendor: text(provides, "vendor"),
+                id: text(provides, "id"),
+            })
+        })
+        .collect()
+}
+
+/// Expands the `${user.home}` and `${env.NAME}` expressions in a value.
+///
+/// # Returns
+/// Some containing the expanded value, otherwise None if an expression can't be expanded.
+fn expand(sysroot: &Sysroot, value: &str, user_home: Option<&Path>) -> Option<String> {
+    let mut expanded = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        let end = start + rest[start..].find('}')?;
+        let expression = &rest[start + 2..end];
+        let replacement = match expression.strip_prefix("env.") {
+            Some(name) => sysroot.var_os(name)?.into_string().ok()?,
+            None if expression == "user.home" => user_home?.to_string_lossy().into_owned(),
+            None => return None,
+        };
+        expanded.push_str(&rest[..start]);
+        expanded.push_str(&replacement);
+        rest = &rest[end + 1..];
+    }
+    expanded.push_str(rest);
+    Some(expanded)
+}
+
+/// Checks a declared toolchain version against an install.
+///
+/// Version ranges, such as `[11,17)`, are not checked. Versions more precise than
+/// the language version, such as `17.0.10`, must prefix the runtime version.
+fn version_matches(declared: &str, install: &JavaInstall) -> bool {
+    if declared.starts_with(['[', '(']) || declared.contains(',') {
+        return true;
+    }
+    if JavaVersion::parse(declared).is_some_and(|e| e != install.lang_version) {
+        return false;
+    }
+
+    let precise = declared.split('.').count() > if declared.starts_with("1.") { 2 } else { 1 };
+    !precise || [&install.runtime_version, &install.impl_version].iter().any(|e| e.starts_with(declared))
+}
+
+/// Checks a declared toolchain vendor against an install, case insensitively.
+///
+/// The vendor may name the install's vendor string, such as `Eclipse Adoptium`, or a
+/// known vendor, such as `temurin`.
+fn vendor_matches(declared: &str, install: &JavaInstall) -> bool {
+    let declared = declared.to_lowercase();
+    install.vendor.to_lowercase().contains(&declared)
+        || install.known_vendor.is_some_and(|e| format!("{:?}", e).to_lowercase() == declared)
+}
@@ -0,0 +1,141 @@
+use crate::locator::sysroot::{Sysroot, UserDirs};
+use crate::install::ProbeError;
+use crate::locator::{add_candidate, add_candidate_or_child, is_java_home, list_dir, reject_candidate, JavaCandidate, JavaLocator};
+use std::path::{Path, PathBuf};
+
+/// The metadata key for the Minecraft launcher which downloaded an install, such as `Minecraft Launcher`.
+pub const MINECRAFT_LAUNCHER: &str = "minecraft.launcher";
+/// The metadata key for the Mojang runtime component of an install, such as `java-runtime-delta`.
+pub const MINECRAFT_COMPONENT: &str = "minecraft.component";
+/// The metadata key for the Mojang runtime platform of an install, such as `linux` or `windows-x64`.
+pub const MINECRAFT_PLATFORM: &str = "minecraft.platform";
+/// The metadata key for the version of a Mojang runtime, as recorded by the launcher, such as `17.0.8`.
+pub const MINECRAFT_VERSION: &str = "minecraft.version";
+
+/// The package name of the Microsoft Store release of the Minecraft launcher.
+#[cfg(target_os = "windows")]
+const STORE_PACKAGE: &str = "Microsoft.4297127D64EC6_8wekyb3d8bbwe";
+
+/// A JavaLocator capable of locating the Java runtimes downloaded by Minecraft launchers.
+///
+/// The official launcher keeps Mojang runtimes in `runtime/<component>/<platform>/<component>`,
+/// next to `<component>.version` and `<component>.sha1` files. Runtimes whose `.sha1`
+/// manifest lists missing files are partial downloads and are skipped.
+///
+/// The `java` folders of Prism Launcher and MultiMC are also searched.
+#[derive(Default)]
+pub struct MinecraftJavaLocator {}
+
+impl MinecraftJavaLocator {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    fn runtime_dirs(sysroot: &Sysroot) -> Vec<PathBuf> {
+        let mut dirs: Vec<PathBuf> = Vec::new();
+        for user in sysroot.users() {
+            if cfg!(target_os = "linux") {
+                dirs.push(user.home.join(".minecraft/runtime"));
+            } else if cfg!(target_os = "macos") {
+                dirs.push(user.data.join("minecraft/runtime"));
+            } else {
+                dirs.push(user.data.join(".minecraft/runtime"));
+            }
+
+            // The Microsoft Store launcher.
+            #[cfg(target_os = "windows")]
+            dirs.push(user.data_local.join("Packages").join(STORE_PACKAGE).join("LocalCache/Local/runtime"));
+        }
+
+        // The legacy launcher installer.
+        #[cfg(target_os = "windows")]
+        dirs.push(sysroot.path("C:/Program Files (x86)/Minecraft Launcher/runtime"));
+        dirs
+    }
+
+    fn launcher_dirs(user: &UserDirs) -> Vec<(PathBuf, &'static str)> {
+        vec![
+            (user.data.join("PrismLauncher/java"), "Prism Launcher"),
+            (user.data.join("multimc/java"), "MultiMC"),
+        ]
+    }
+}
+
+impl JavaLocator for MinecraftJavaLocator {
+    fn name(&self) -> &'static str {
+        "Minecraft"
+    }
+
+    fn find_candidates(&self, sysroot: &Sysroot) -> Option<Vec<JavaCandidate>> {
+        let mut vec: Vec<JavaCandidate> = Vec::new();
+        for dir in Self::runtime_dirs(sysroot) {
+            log_debug!("Searching for JVM's downloaded by the Minecraft launcher in path: {:?}", &dir);
+            for component in list_dir(sysroot, &dir) {
+                for platform in list_dir(sysroot, component.path()) {
+                    add_runtime(&mut vec, sysroot, &component.path(), &platform.path());
+                }
+            }
+        }
+
+        for (dir, launcher) in sysroot.users().iter().flat_map(Self::launcher_dirs) {
+            log_debug!("Searching for JVM's downloaded by {} in path: {:?}", launcher, &dir);
+            for entry in list_dir(sysroot, &dir) {
+                let path = entry.path();
+                let Some(candidate) = add_candidate_or_child(&mut vec, sysroot, &path) else {
+                    continue;
+                };
+                candidate.with_metadata(MINECRAFT_LAUNCHER, launcher);
+                // These launchers may also download Mojang runtimes, named after their component.
+                if let Some(name) = path.file_name().and_then(|e| e.to_str()).filter(|e| is_mojang_component(e)) {
+                    candidate.with_metadata(MINECRAFT_COMPONENT, name);
+                }
+            }
+        }
+        Some(vec)
+    }
+}
+
+/// Adds the runtime in `<component>/<platform>/<component>`.
+fn add_runtime(vec: &mut Vec<JavaCandidate>, sysroot: &Sysroot, component_dir: &Path, platform_dir: &Path) {
+    let (Some(component), Some(platform)) = (component_dir.file_name(), platform_dir.file_name()) else {
+        return;
+    };
+    let component = component.to_string_lossy();
+    let runtime = platform_dir.join(&*component);
+
+    let manifest = platform_dir.join(format!("{}.sha1", component));
+    if let Some(missing) = find_missing_file(sysroot, &runtime, &manifest) {
+        reject_candidate(vec, sysroot, &runtime, ProbeError::MissingFile(missing));
+        return;
+    }
+
+    // macOS runtimes are bundles, `jre.bundle/Contents/Home`.
+    let bundle = runtime.join("jre.bundle/Contents/Home");
+    let home = if is_java_home(sysroot, &bundle) { bundle } else { runtime };
+    let Some(candidate) = add_candidate(vec, sysroot, &home) else {
+        return;
+    };
+    candidate.with_metadata(MINECRAFT_LAUNCHER, "Minecraft Launcher");
+    candidate.with_metadata(MINECRAFT_COMPONENT, &*component);
+    candidate.with_metadata(MINECRAFT_PLATFORM, platform.to_string_lossy());
+    if let Ok(version) = sysroot.read_to_string(platform_dir.join(format!("{}.version", component))) {
+        candidate.with_metadata(MINECRAFT_VERSION, version.trim());
+    }
+}
+
+/// Finds the first file listed in a runtime's `.sha1` manifest which doesn't exist.
+///
+/// Each line of the manifest is `<path> /// <sha1> <timestamp>`, relative to the runtime.
+/// Runtimes without a manifest are assumed to be complete.
+fn find_missing_file(sysroot: &Sysroot, runtime: &Path, manifest: &Path) -> Option<PathBuf> {
+    let contents = sysroot.read_to_string(manifest).ok()?;
+    contents.lines()
+        .filter_map(|line| line.split_once(" /// "))
+        .map(|(path, _)| runtime.join(path))
+        .find(|path| !sysroot.exists(path))
+}
+
+/// Checks if the given name is a Mojang runtime component, such as `java-runtime-delta` or `jre-legacy`.
+fn is_mojang_component(name: &str) -> bool {
+    name.starts_with("java-runtime-") || name == "jre-legacy"
+}
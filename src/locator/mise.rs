@@ -0,0 +1,45 @@
+use crate::locator::sysroot::Sysroot;
+use crate::locator::{scan_aliased_folder, JavaCandidate, JavaLocator};
+use std::path::PathBuf;
+
+/// The metadata key for the mise version name of an install, such as `temurin-17.0.10+7`.
+pub const MISE_VERSION: &str = "mise.version";
+
+/// A JavaLocator capable of locating JVM's installed by mise.
+///
+/// mise creates symlinked aliases such as `latest` and `17` next to the real installs,
+/// these are resolved so each install is only found once.
+#[derive(Default)]
+pub struct MiseJavaLocator {}
+
+impl MiseJavaLocator {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    fn data_dirs(sysroot: &Sysroot) -> Vec<PathBuf> {
+        if let Some(dir) = sysroot.var_os("MISE_DATA_DIR") {
+            return vec![PathBuf::from(dir)];
+        }
+        // mise uses the XDG layout on every platform, including macOS.
+        sysroot.dirs_from_env_or_home("XDG_DATA_HOME", ".local/share").into_iter()
+            .map(|e| e.join("mise"))
+            .collect()
+    }
+}
+
+impl JavaLocator for MiseJavaLocator {
+    fn name(&self) -> &'static str {
+        "mise"
+    }
+
+    fn find_candidates(&self, sysroot: &Sysroot) -> Option<Vec<JavaCandidate>> {
+        let mut vec: Vec<JavaCandidate> = Vec::new();
+        for dir in Self::data_dirs(sysroot) {
+            let dir = dir.join("installs/java");
+            log_debug!("Searching for JVM's installed by mise in path: {:?}", &dir);
+            scan_aliased_folder(&mut vec, sysroot, &dir, MISE_VERSION);
+        }
+        Some(vec)
+    }
+}
@@ -0,0 +1,161 @@
+use crate::install::JavaInstall;
+use crate::locator::environment::resolve_home;
+use crate::locator::sysroot::Sysroot;
+use crate::locator::{add_candidate, is_java_home, list_dir, JavaCandidate, JavaLocator};
+use std::path::{Component, Path, PathBuf};
+
+/// The metadata key for the Nix store path an install lives in.
+pub const NIX_STORE_PATH: &str = "nix.store_path";
+/// The metadata key for the Nix profile or GC root an install was found through.
+pub const NIX_ROOT: &str = "nix.root";
+
+/// System wide profiles.
+const SYSTEM_PROFILES: &[&str] = &[
+    "/run/current-system/sw",
+    "/nix/var/nix/profiles/default",
+];
+
+/// Automatic GC roots, created for `result` links and `nix-shell`/direnv environments.
+const AUTO_GC_ROOTS: &str = "/nix/var/nix/gcroots/auto";
+
+/// A JavaLocator capable of locating JVM's installed with Nix.
+///
+/// Only JVM's reachable from user and system profiles, or automatic GC roots, are found.
+/// Walking the entire Nix store is far too slow, and would find every JVM ever built.
+#[derive(Default)]
+pub struct NixJavaLocator {}
+
+impl NixJavaLocator {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    fn store_dir(sysroot: &Sysroot) -> PathBuf {
+        let dir = sysroot.var_os("NIX_STORE_DIR").map_or_else(|| PathBuf::from("/nix/store"), PathBuf::from);
+        sysroot.path(dir)
+    }
+
+    fn profiles(sysroot: &Sysroot) -> Vec<PathBuf> {
+        let mut profiles: Vec<PathBuf> = Vec::new();
+        for user in sysroot.users() {
+            profiles.push(user.home.join(".nix-profile"));
+            profiles.extend(user.state.map(|e| e.join("nix/profile")));
+        }
+
+        // Within a sysroot, every user's profile is searched.
+        let per_user = [sysroot.path("/etc/profiles/per-user"), sysroot.path("/nix/var/nix/profiles/per-user")];
+        match sysroot.var_os("USER") {
+            Some(user) => {
+                profiles.push(per_user[0].join(&user));
+                profiles.push(per_user[1].join(&user).join("profile"));
+            }
+            None if !sysroot.is_host() => {
+                profiles.extend(list_dir(sysroot, &per_user[0]).into_iter().map(|e| e.path()));
+                profiles.extend(list_dir(sysroot, &per_user[1]).into_iter().map(|e| e.path().join("profile")));
+            }
+            None => {}
+        }
+        profiles.extend(SYSTEM_PROFILES.iter().map(|e| sysroot.path(e)));
+        profiles
+    }
+}
+
+impl JavaLocator for NixJavaLocator {
+    fn name(&self) -> &'static str {
+        "Nix"
+    }
+
+    fn find_candidates(&self, sysroot: &Sysroot) -> Option<Vec<JavaCandidate>> {
+        let store_dir = Self::store_dir(sysroot);
+        let mut vec: Vec<JavaCandidate> = Vec::new();
+
+        log_debug!("Searching for JVM's installed in Nix profiles.");
+        for profile in Self::profiles(sysroot) {
+            let executable = JavaInstall::get_java_executable(&profile, false);
+            let Some(home) = resolve_home(sysroot, &executable) else {
+                continue;
+            };
+            add_store_candidate(&mut vec, sysroot, &store_dir, &home, &profile);
+        }
+
+        let gc_roots = sysroot.path(AUTO_GC_ROOTS);
+        log_debug!("Searching for JVM's referenced by Nix GC roots in path: {:?}", &gc_roots);
+        for root in list_dir(sysroot, &gc_roots) {
+            let root = root.path();
+            let Ok(target) = sysroot.canonicalize(&root) else {
+                continue;
+            };
+            add_store_candidate(&mut vec, sysroot, &store_dir, &target, &root);
+        }
+        Some(vec)
+    }
+}
+
+/// Adds a candidate for a path within the Nix store.
+///
+/// JDK packages keep the real home in `lib/openjdk`, with `bin` links at the top
+/// of the store path. The `lib/openjdk` home is preferred when it exists. Paths
+/// which aren't a Java installation, such as other GC roots, are skipped.
+///
+/// * `path` - A path within a store path.
+/// * `root` - The profile or GC root the path was found through.
+fn add_store_candidate(vec: &mut Vec<JavaCandidate>, sysroot: &Sysroot, store_dir: &Path, path: &Path, root: &Path) {
+    let Some(store_path) = store_path(store_dir, path) else {
+        return;
+    };
+
+    let Some(home) = [store_path.join("lib/openjdk"), path.to_path_buf()].into_iter()
+        .find(|e| is_java_home(sysroot, e) || is_java_home(sysroot, JavaInstall::get_home_dir(e))) else {
+        log_debug!("Skipping Nix store path without a JVM: {:?}", path);
+        return;
+    };
+
+    if let Some(candidate) = add_candidate(vec, sysroot, &home) {
+        // Within a sysroot, these are paths on the searched system.
+        if let Some(store_path) = sysroot.target_path(&store_path) {
+            candidate.with_metadata(NIX_STORE_PATH, store_path.to_string_lossy());
+        }
+        if let Some(root) = sysroot.target_path(root) {
+            candidate.metadata.entry(NIX_ROOT.into()).or_insert_with(|| root.to_string_lossy().into_owned());
+        }
+    }
+}
+
+/// Gets the top level store path containing the given path, `/nix/store/<hash>-<name>`.
+fn store_path(store_dir: &Path, path: &Path) -> Option<PathBuf> {
+    let relative = path.strip_prefix(store_dir).ok()?;
+    match relative.components().next()? {
+        Component::Normal(name) => Some(store_dir.join(name)),
+        _ => None,
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::os::unix::fs::symlink;
+    use tempfile::TempDir;
+
+    #[test]
+    fn skips_gc_roots_without_jvm() {
+        let temp = TempDir::new().unwrap();
+        let root = fs::canonicalize(temp.path()).unwrap();
+        let jdk = root.join("nix/store/abc-openjdk-17.0.10/lib/openjdk");
+        fs::create_dir_all(jdk.join("bin")).unwrap();
+        fs::write(JavaInstall::get_java_executable(&jdk, true), "").unwrap();
+        fs::create_dir_all(root.join("nix/store/def-shell-env/bin")).unwrap();
+
+        let gc_roots = root.join("nix/var/nix/gcroots/auto");
+        fs::create_dir_all(&gc_roots).unwrap();
+        symlink("/nix/store/abc-openjdk-17.0.10", gc_roots.join("jdk")).unwrap();
+        symlink("/nix/store/def-shell-env", gc_roots.join("shell")).unwrap();
+
+        let candidates = NixJavaLocator::new().find_candidates(&Sysroot::new(&root)).unwrap();
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].path, jdk);
+        assert!(candidates[0].rejection.is_none());
+        assert_eq!(candidates[0].metadata[NIX_STORE_PATH], "/nix/store/abc-openjdk-17.0.10");
+        assert_eq!(candidates[0].metadata[NIX_ROOT], "/nix/var/nix/gcroots/auto/jdk");
+    }
+}
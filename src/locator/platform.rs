@@ -5,6 +5,31 @@ mod macos;
 #[cfg(target_os = "windows")]
 mod windows;
 
+/// The metadata key marking the JVM the system selects by default, such as through
+/// `/etc/alternatives/java`.
+pub const SYSTEM_DEFAULT: &str = "system.default";
+/// The metadata key for the Debian package which owns a JVM, such as `openjdk-17-jre-headless`.
+pub const DEBIAN_PACKAGE: &str = "debian.package";
+/// The metadata key for the alias of a JVM, from its Debian `.jinfo` file.
+pub const DEBIAN_ALIAS: &str = "debian.alias";
+/// The metadata key for the alternatives priority of a JVM, from its Debian `.jinfo` file.
+pub const DEBIAN_PRIORITY: &str = "debian.priority";
+
+/// Common disk locations of JVM's on Windows. These are shared with the WSL locator,
+/// which finds them through the drive mounts.
+pub(crate) const WINDOWS_PATHS: &[&str] = &[
+    "C:/Program Files/AdoptOpenJDK/",
+    "C:/Program Files/Eclipse Foundation/",
+    "C:/Program Files/Eclipse Adoptium/",
+    "C:/Program Files/Java/",
+    "C:/Program Files/Microsoft/",
+    "C:/Program Files (x86)/AdoptOpenJDK/",
+    "C:/Program Files (x86)/Eclipse Foundation/",
+    "C:/Program Files (x86)/Eclipse Adoptium/",
+    "C:/Program Files (x86)/Java",
+    "C:/Program Files (x86)/Microsoft/",
+];
+
 /// A JavaLocator capable of locating JVM's installed in known platform
 /// specific system paths or registry locations.
 #[derive(Default)]
@@ -1,26 +1,110 @@
-use crate::install::JavaInstall;
-use crate::locator::platform::PlatformJavaLocator;
-use crate::locator::{scan_folder, JavaLocator};
+use crate::locator::environment::resolve_home;
+use crate::locator::platform::{PlatformJavaLocator, DEBIAN_ALIAS, DEBIAN_PACKAGE, DEBIAN_PRIORITY, SYSTEM_DEFAULT};
+use crate::locator::sysroot::Sysroot;
+use crate::locator::{add_candidate, list_dir, scan_folder, JavaCandidate, JavaLocator};
 use crate::log_debug;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+/// The directories distros install JVM's into, which may contain `.jinfo` files.
+const DISTRO_DIRS: &[&str] = &["/usr/lib/jvm", "/usr/lib32/jvm"];
+
+/// The alternatives symlink for the system default java executable.
+const JAVA_ALTERNATIVE: &str = "/etc/alternatives/java";
+
+/// The directory dpkg stores the file lists of installed packages in.
+const DPKG_INFO: &str = "/var/lib/dpkg/info";
 
 impl JavaLocator for PlatformJavaLocator {
-    fn locate(&self) -> Option<Vec<JavaInstall>> {
+    fn name(&self) -> &'static str {
+        "Linux"
+    }
+
+    fn find_candidates(&self, sysroot: &Sysroot) -> Option<Vec<JavaCandidate>> {
         log_debug!("Searching for JVM's installed in common system locations.");
 
-        let mut vec: Vec<JavaInstall> = Vec::new();
+        // Distros symlink aliases next to the real homes, such as `default-java`,
+        // these collapse into their canonical home.
+        let mut vec: Vec<JavaCandidate> = Vec::new();
         // Oracle
-        scan_folder(&mut vec, Path::new("/usr/java"));
+        scan_folder(&mut vec, sysroot, sysroot.path("/usr/java"));
 
         // Common distro locations
-        scan_folder(&mut vec, Path::new("/usr/lib/jvm"));
-        scan_folder(&mut vec, Path::new("/usr/lib32/jvm"));
+        for dir in DISTRO_DIRS {
+            scan_folder(&mut vec, sysroot, sysroot.path(dir));
+        }
 
         // Manually installed locations
-        scan_folder(&mut vec, Path::new("/opt/jdk"));
-        scan_folder(&mut vec, Path::new("/opt/jdks"));
+        scan_folder(&mut vec, sysroot, sysroot.path("/opt/jdk"));
+        scan_folder(&mut vec, sysroot, sysroot.path("/opt/jdks"));
+
+        for user in sysroot.users() {
+            scan_folder(&mut vec, sysroot, user.home.join(".local/jdks"));
+        }
+
+        if let Some(home) = resolve_home(sysroot, &sysroot.path(JAVA_ALTERNATIVE)) {
+            log_debug!("System default JVM is {:?}.", &home);
+            if let Some(candidate) = add_candidate(&mut vec, sysroot, &home) {
+                candidate.with_metadata(SYSTEM_DEFAULT, "true");
+            }
+        }
 
-        scan_folder(&mut vec, &dirs::home_dir()?.join(".local/jdks"));
+        for dir in DISTRO_DIRS {
+            for info in read_jinfo_files(sysroot, &sysroot.path(dir)) {
+                let Some(candidate) = vec.iter_mut().find(|e| e.path == info.home) else {
+                    continue;
+                };
+                candidate.with_metadata(DEBIAN_ALIAS, info.alias);
+                candidate.with_metadata(DEBIAN_PRIORITY, info.priority);
+                if let Some(package) = find_dpkg_package(sysroot, &info.home) {
+                    candidate.with_metadata(DEBIAN_PACKAGE, package);
+                }
+            }
+        }
         Some(vec)
     }
 }
+
+/// The contents of a Debian `.jinfo` file, describing a JVM package.
+struct JInfo {
+    home: PathBuf,
+    alias: String,
+    priority: String,
+}
+
+/// Reads all `.<name>.jinfo` files in the given directory.
+fn read_jinfo_files(sysroot: &Sysroot, dir: &Path) -> Vec<JInfo> {
+    list_dir(sysroot, dir).into_iter()
+        .map(|e| e.path())
+        .filter(|e| e.extension().is_some_and(|ext| ext == "jinfo"))
+        .filter_map(|e| {
+            let contents = sysroot.read_to_string(&e).ok()?;
+            let value = |key: &str| contents.lines()
+                .filter_map(|line| line.split_once('='))
+                .find(|(k, _)| k.trim() == key)
+                .map(|(_, v)| v.trim().to_string());
+
+            Some(JInfo {
+                home: sysroot.canonicalize(dir.join(value("name")?)).ok()?,
+                alias: value("alias").unwrap_or_default(),
+                priority: value("priority").unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+/// Finds the dpkg package which owns the java executable of the given home.
+///
+/// Only the file lists of packages with a jdk or jre in their name are searched.
+fn find_dpkg_package(sysroot: &Sysroot, home: &Path) -> Option<String> {
+    // The file lists contain paths on the searched system.
+    let executable = sysroot.target_path(home)?.join("bin/java");
+    let executable = executable.to_str()?;
+    list_dir(sysroot, sysroot.path(DPKG_INFO)).into_iter()
+        .map(|e| e.path())
+        .filter(|e| e.extension().is_some_and(|ext| ext == "list"))
+        .filter_map(|e| Some((e.file_stem()?.to_str()?.to_string(), e)))
+        .filter(|(name, _)| name.contains("jdk") || name.contains("jre"))
+        .find(|(_, list)| sysroot.read_to_string(list).is_ok_and(|e| e.lines().any(|line| line == executable)))
+        // Strip the architecture qualifier, `openjdk-17-jre-headless:amd64`.
+        .map(|(name, _)| name.split(':').next().unwrap_or(&name).to_string())
+}
@@ -1,16 +1,19 @@
-use crate::install::JavaInstall;
 use crate::locator::platform::PlatformJavaLocator;
-use crate::locator::{scan_folder, JavaLocator};
+use crate::locator::sysroot::Sysroot;
+use crate::locator::{scan_folder, JavaCandidate, JavaLocator};
 use crate::log_debug;
-use std::path::Path;
 
 impl JavaLocator for PlatformJavaLocator {
-    fn locate(&self) -> Option<Vec<JavaInstall>> {
+    fn name(&self) -> &'static str {
+        "macOS"
+    }
+
+    fn find_candidates(&self, sysroot: &Sysroot) -> Option<Vec<JavaCandidate>> {
         log_debug!("Searching for JVM's installed in common system locations.");
 
-        let mut vec: Vec<JavaInstall> = Vec::new();
-        scan_folder(&mut vec, Path::new("/Library/Java/JavaVirtualMachines/"));
-        scan_folder(&mut vec, Path::new("/System/Library/Java/JavaVirtualMachines/"));
+        let mut vec: Vec<JavaCandidate> = Vec::new();
+        scan_folder(&mut vec, sysroot, sysroot.path("/Library/Java/JavaVirtualMachines/"));
+        scan_folder(&mut vec, sysroot, sysroot.path("/System/Library/Java/JavaVirtualMachines/"));
         Some(vec)
     }
 }
@@ -1,6 +1,6 @@
-use crate::install::JavaInstall;
-use crate::locator::platform::PlatformJavaLocator;
-use crate::locator::{find_add_install, scan_folder, JavaLocator};
+use crate::locator::platform::{PlatformJavaLocator, WINDOWS_PATHS};
+use crate::locator::sysroot::Sysroot;
+use crate::locator::{add_candidate, scan_folder, JavaCandidate, JavaLocator};
 use crate::log_debug;
 use winreg::enums::HKEY_LOCAL_MACHINE;
 use winreg::RegKey;
@@ -45,20 +45,6 @@ const MICROSOFT: &'static [&'static str] = &[
     /*HKEY_LOCAL_MACHINE\\*/ "SOFTWARE\\Wow6432Node\\Microsoft\\JRE",
 ];
 
-// Common disk locations.
-const PATHS: &'static [&'static str] = &[
-    "C:/Program Files/AdoptOpenJDK/",
-    "C:/Program Files/Eclipse Foundation/",
-    "C:/Program Files/Eclipse Adoptium/",
-    "C:/Program Files/Java/",
-    "C:/Program Files/Microsoft/",
-    "C:/Program Files (x86)/AdoptOpenJDK/",
-    "C:/Program Files (x86)/Eclipse Foundation/",
-    "C:/Program Files (x86)/Eclipse Adoptium/",
-    "C:/Program Files (x86)/Java",
-    "C:/Program Files (x86)/Microsoft/",
-];
-
 fn get_sub_keys(hklm: &RegKey, key: &str) -> Vec<String> {
     if let Ok(opened) = hklm.open_subkey(key) {
         return opened.enum_keys()
@@ -69,29 +55,35 @@ fn get_sub_keys(hklm: &RegKey, key: &str) -> Vec<String> {
     Vec::new()
 }
 
-fn scan_registry(mut vec: &mut Vec<JavaInstall>, keys: impl IntoIterator<Item=impl AsRef<str>>, key_suffix: &str, path_key: &str) {
+fn scan_registry(mut vec: &mut Vec<JavaCandidate>, sysroot: &Sysroot, keys: impl IntoIterator<Item=impl AsRef<str>>, key_suffix: &str, path_key: &str) {
     let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
     keys.into_iter()
         .flat_map(|k| get_sub_keys(&hklm, k.as_ref()))
         .filter_map(|e| hklm.open_subkey(e + "\\" + key_suffix).ok())
         .filter_map(|e| e.get_value::<String, _>(path_key).ok())
-        .for_each(|e| { find_add_install(&mut vec, &e); });
+        .for_each(|e| { add_candidate(&mut vec, sysroot, &e); });
 }
 
 impl JavaLocator for PlatformJavaLocator {
-    fn locate(&self) -> Option<Vec<JavaInstall>> {
-        let mut vec: Vec<JavaInstall> = Vec::new();
-        // Search known registry keys.
-        log_debug!("Searching for JVM's installed in common system registry locations.");
-        scan_registry(&mut vec, ORACLE, "", "JavaHome");
-        scan_registry(&mut vec, ADOPT_OPEN_JDK, "hotspot\\MSI", "Path");
-        scan_registry(&mut vec, ADOPTIUM, "hotspot\\MSI", "Path");
-        scan_registry(&mut vec, MICROSOFT, "hotspot\\MSI", "Path");
+    fn name(&self) -> &'static str {
+        "Windows"
+    }
+
+    fn find_candidates(&self, sysroot: &Sysroot) -> Option<Vec<JavaCandidate>> {
+        let mut vec: Vec<JavaCandidate> = Vec::new();
+        // Search known registry keys, only the host's registry is available.
+        if sysroot.is_host() {
+            log_debug!("Searching for JVM's installed in common system registry locations.");
+            scan_registry(&mut vec, sysroot, ORACLE, "", "JavaHome");
+            scan_registry(&mut vec, sysroot, ADOPT_OPEN_JDK, "hotspot\\MSI", "Path");
+            scan_registry(&mut vec, sysroot, ADOPTIUM, "hotspot\\MSI", "Path");
+            scan_registry(&mut vec, sysroot, MICROSOFT, "hotspot\\MSI", "Path");
+        }
 
         // Try again in known paths.
         log_debug!("Searching for JVM's installed in common system locations.");
-        for path in PATHS {
-            scan_folder(&mut vec, path);
+        for path in WINDOWS_PATHS {
+            scan_folder(&mut vec, sysroot, sysroot.path(path));
         }
 
         Some(vec)
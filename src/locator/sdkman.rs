@@ -0,0 +1,82 @@
+use crate::locator::sysroot::Sysroot;
+use crate::locator::{add_candidate, list_dir, JavaCandidate, JavaLocator};
+use std::path::{Path, PathBuf};
+
+/// The metadata key for the SDKMAN! candidate identifier of an install, such as `17.0.10-tem`.
+pub const SDKMAN_IDENTIFIER: &str = "sdkman.identifier";
+
+/// A JavaLocator capable of locating JVM's installed by SDKMAN!.
+///
+/// The `current` symlink is skipped, as it always points to another candidate. Local
+/// versions, registered with `sdk install java <version> <path>`, are symlinks to
+/// their home and are found like any other candidate.
+#[derive(Default)]
+pub struct SdkmanJavaLocator {}
+
+impl SdkmanJavaLocator {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    fn sdkman_dirs(sysroot: &Sysroot) -> Vec<PathBuf> {
+        sysroot.dirs_from_env_or_home("SDKMAN_DIR", ".sdkman")
+    }
+}
+
+impl JavaLocator for SdkmanJavaLocator {
+    fn name(&self) -> &'static str {
+        "SDKMAN!"
+    }
+
+    fn find_candidates(&self, sysroot: &Sysroot) -> Option<Vec<JavaCandidate>> {
+        let mut vec: Vec<JavaCandidate> = Vec::new();
+        for dir in Self::sdkman_dirs(sysroot) {
+            let dir = dir.join("candidates/java");
+            log_debug!("Searching for JVM's installed by SDKMAN! in path: {:?}", &dir);
+            scan_candidates(&mut vec, sysroot, &dir);
+        }
+        Some(vec)
+    }
+}
+
+/// Adds each candidate in the `candidates/java` directory, skipping `current`.
+fn scan_candidates(vec: &mut Vec<JavaCandidate>, sysroot: &Sysroot, dir: &Path) {
+    for entry in list_dir(sysroot, dir) {
+        let path = entry.path();
+        let Some(identifier) = path.file_name().and_then(|e| e.to_str()).map(str::to_owned) else {
+            continue;
+        };
+        if identifier == "current" || !sysroot.is_dir(&path) {
+            continue;
+        }
+
+        if let Some(candidate) = add_candidate(vec, sysroot, &path) {
+            candidate.with_metadata(SDKMAN_IDENTIFIER, identifier);
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::install::JavaInstall;
+    use std::fs;
+    use std::os::unix::fs::symlink;
+    use tempfile::TempDir;
+
+    #[test]
+    fn follows_links_within_sysroot() {
+        let temp = TempDir::new().unwrap();
+        let root = fs::canonicalize(temp.path()).unwrap();
+        let home = root.join("opt/sdkman/candidates/java/17.0.10-tem");
+        fs::create_dir_all(home.join("bin")).unwrap();
+        fs::write(JavaInstall::get_java_executable(&home, true), "").unwrap();
+        fs::create_dir_all(root.join("root")).unwrap();
+        symlink("/opt/sdkman", root.join("root/.sdkman")).unwrap();
+
+        let candidates = SdkmanJavaLocator::new().find_candidates(&Sysroot::new(&root)).unwrap();
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].path, home);
+        assert_eq!(candidates[0].metadata[SDKMAN_IDENTIFIER], "17.0.10-tem");
+    }
+}
@@ -0,0 +1,285 @@
+use crate::install::{Architecture, JavaInstall, JavaVersion, Vendor};
+use crate::locator::directory::DirectoryJavaLocator;
+use crate::locator::environment::EnvironmentJavaLocator;
+use crate::locator::platform::PlatformJavaLocator;
+use crate::locator::wsl::WslJavaLocator;
+use crate::locator::JavaLocator;
+use std::cmp::Ordering;
+use std::iter;
+use std::ops::{Bound, RangeBounds};
+
+/// The Java installations a selection may choose from.
+#[derive(Debug, Clone)]
+pub struct JavaRequirement {
+    versions: (Bound<JavaVersion>, Bound<JavaVersion>),
+    jdk: bool,
+}
+
+impl JavaRequirement {
+    /// A requirement satisfied by any installation.
+    pub fn any() -> Self {
+        Self::version_range(..)
+    }
+
+    /// A requirement for the given language version.
+    ///
+    /// * `version` - The language version required.
+    pub fn version(version: JavaVersion) -> Self {
+        Self::version_range(version..=version)
+    }
+
+    /// A requirement for a language version in the given range.
+    ///
+    /// * `range` - The versions accepted, such as `JavaVersion::Java11..` for 11 or newer.
+    pub fn version_range(range: impl RangeBounds<JavaVersion>) -> Self {
+        Self {
+            versions: (range.start_bound().cloned(), range.end_bound().cloned()),
+            jdk: false,
+        }
+    }
+
+    /// Require the installation to contain a compiler.
+    ///
+    /// # Returns
+    /// The same requirement, for chaining.
+    pub fn jdk_only(&mut self) -> &mut Self {
+        self.jdk = true;
+        self
+    }
+
+    /// Checks if the given installation satisfies this requirement.
+    pub fn matches(&self, install: &JavaInstall) -> bool {
+        self.versions.contains(&install.lang_version) && (!self.jdk || install.is_jdk)
+    }
+}
+
+/// A criterion installations are ranked by, see [`SelectionPolicy`].
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum RankingCriterion {
+    /// Installations running natively on this machine rank above those which need
+    /// emulation, such as x86_64 on aarch64.
+    NativeArchitecture,
+    /// JDK's rank above JRE's.
+    Jdk,
+    /// Installations from a preferred vendor rank above others, in the order they
+    /// are preferred.
+    PreferredVendor,
+    /// Newer language versions rank above older ones, then newer patches of the
+    /// same language version.
+    NewestVersion,
+    /// Installations managed by a tool, such as SDKMAN! or Gradle, rank above those
+    /// only found installed on the system, through the environment or in a directory.
+    Managed,
+}
+
+/// How installations are ranked when selecting between them.
+///
+/// Installations are compared by each criterion in turn, later criteria only break
+/// ties. Installations which tie on every criterion keep their discovery order.
+#[derive(Debug, Clone)]
+pub struct SelectionPolicy {
+    criteria: Vec<RankingCriterion>,
+    preferred_vendors: Vec<Vendor>,
+    unmanaged_locators: Vec<String>,
+}
+
+impl Default for SelectionPolicy {
+    fn default() -> Self {
+        Self {
+            criteria: vec![
+                RankingCriterion::NativeArchitecture,
+                RankingCriterion::Jdk,
+                RankingCriterion::PreferredVendor,
+                RankingCriterion::NewestVersion,
+                RankingCriterion::Managed,
+            ],
+            preferred_vendors: Vec::new(),
+            unmanaged_locators: [
+                PlatformJavaLocator::new().name(),
+                WslJavaLocator::new().name(),
+                EnvironmentJavaLocator::new().name(),
+                DirectoryJavaLocator::new().name(),
+            ].map(String::from).to_vec(),
+        }
+    }
+}
+
+impl SelectionPolicy {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Replace the criteria installations are ranked by.
+    ///
+    /// * `criteria` - The criteria, most important first.
+    pub fn criteria(&mut self, criteria: impl IntoIterator<Item = RankingCriterion>) -> &mut Self {
+        self.criteria = criteria.into_iter().collect();
+        self
+    }
+
+    /// Prefer installations from the given vendor, after any already preferred.
+    pub fn prefer_vendor(&mut self, vendor: Vendor) -> &mut Self {
+        if !self.preferred_vendors.contains(&vendor) {
+            self.preferred_vendors.push(vendor);
+        }
+        self
+    }
+
+    /// Treat installations only found by the given locator as unmanaged. By default,
+    /// these are the platform, WSL, environment and directory locators.
+    ///
+    /// * `name` - The name of the locator, see [`JavaLocator::name`].
+    pub fn unmanaged_locator(&mut self, name: impl Into<String>) -> &mut Self {
+        self.unmanaged_locators.push(name.into());
+        self
+    }
+
+    /// Ranks the installations satisfying the given requirement.
+    ///
+    /// * `requirement` - The requirement installations must satisfy, others are dropped.
+    /// * `installs` - The installations to rank, in discovery order.
+    ///
+    /// # Returns
+    /// The installations satisfying the requirement, best first.
+    pub fn rank(&self, requirement: &JavaRequirement, installs: Vec<JavaInstall>) -> Vec<RankedInstall> {
+        let mut installs: Vec<JavaInstall> = installs.into_iter()
+            .filter(|e| requirement.matches(e))
+            .collect();
+        installs.sort_by(|a, b| {
+            self.criteria.iter()
+                .map(|e| self.compare(*e, a, b))
+                .find(|e| e.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+
+        installs.into_iter()
+            .enumerate()
+            .map(|(index, install)| RankedInstall {
+                rank: index + 1,
+                reasons: self.criteria.iter().map(|e| self.explain(*e, &install)).collect(),
+                install,
+            })
+            .collect()
+    }
+
+    /// Compares two installations by a criterion, Less if `a` ranks above `b`.
+    fn compare(&self, criterion: RankingCriterion, a: &JavaInstall, b: &JavaInstall) -> Ordering {
+        match criterion {
+            RankingCriterion::NativeArchitecture => is_native(b).cmp(&is_native(a)),
+            RankingCriterion::Jdk => b.is_jdk.cmp(&a.is_jdk),
+            RankingCriterion::PreferredVendor => self.vendor_preference(a).cmp(&self.vendor_preference(b)),
+            RankingCriterion::NewestVersion => b.lang_version.cmp(&a.lang_version)
+                .then_with(|| compare_versions(version_string(b), version_string(a))),
+            RankingCriterion::Managed => self.is_managed(b).cmp(&self.is_managed(a)),
+        }
+    }
+
+    /// Describes how an installation fared against a criterion.
+    fn explain(&self, criterion: RankingCriterion, install: &JavaInstall) -> String {
+        match criterion {
+            RankingCriterion::NativeArchitecture if is_native(install) => format!("{:?} runs natively", install.architecture),
+            RankingCriterion::NativeArchitecture => format!("{:?} is not native to this machine", install.architecture),
+            RankingCriterion::Jdk if install.is_jdk => "is a JDK".into(),
+            RankingCriterion::Jdk => "is a JRE".into(),
+            RankingCriterion::PreferredVendor => match install.known_vendor {
+                Some(vendor) if self.preferred_vendors.contains(&vendor) => {
+                    format!("vendor {:?} is preferred #{}", vendor, self.vendor_preference(install) + 1)
+                }
+                _ if self.preferred_vendors.is_empty() => "no vendor is preferred".into(),
+                _ => format!("vendor {} is not preferred", &install.vendor),
+            },
+            RankingCriterion::NewestVersion => format!("version {}", version_string(install)),
+            RankingCriterion::Managed if self.is_managed(install) => format!("managed by {}", self.managers(install).join(", ")),
+            RankingCriterion::Managed => "not managed by a tool".into(),
+        }
+    }
+
+    /// Gets the position of an installation's vendor in the preferred vendors, or
+    /// after every preferred vendor if it isn't preferred.
+    fn vendor_preference(&self, install: &JavaInstall) -> usize {
+        install.known_vendor
+            .and_then(|vendor| self.preferred_vendors.iter().position(|e| *e == vendor))
+            .unwrap_or(self.preferred_vendors.len())
+    }
+
+    fn managers<'a>(&self, install: &'a JavaInstall) -> Vec<&'a str> {
+        install.located_by.iter()
+            .filter(|e| !self.unmanaged_locators.contains(e))
+            .map(String::as_str)
+            .collect()
+    }
+
+    fn is_managed(&self, install: &JavaInstall) -> bool {
+        !self.managers(install).is_empty()
+    }
+}
+
+/// A Java installation selected by a [`SelectionPolicy`].
+#[derive(Debug, Clone)]
+pub struct RankedInstall {
+    /// The position of the installation, starting from 1 for the best match.
+    pub rank: usize,
+    /// How the installation fared against each criterion of the policy, in the
+    /// order they were applied.
+    pub reasons: Vec<String>,
+    pub install: JavaInstall,
+}
+
+fn is_native(install: &JavaInstall) -> bool {
+    Architecture::current().is_none_or(|e| e == install.architecture)
+}
+
+fn version_string(install: &JavaInstall) -> &str {
+    if install.runtime_version.is_empty() {
+        &install.impl_version
+    } else {
+        &install.runtime_version
+    }
+}
+
+/// Compares two version strings, such as `17.0.10+7` or `1.8.0_402-b06`.
+///
+/// The versions before the build, `17.0.10`, are compared first with missing
+/// components treated as zero. The builds, `7`, only break ties.
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let (a_version, a_build) = a.split_once(['+', '-', '_']).unwrap_or((a, ""));
+    let (b_version, b_build) = b.split_once(['+', '-', '_']).unwrap_or((b, ""));
+    let (a_version, b_version) = (numeric_components(a_version), numeric_components(b_version));
+
+    let len = a_version.len().max(b_version.len());
+    let padded = |e: Vec<u64>| e.into_iter().chain(iter::repeat(0)).take(len);
+    padded(a_version).cmp(padded(b_version))
+        .then_with(|| numeric_components(a_build).cmp(&numeric_components(b_build)))
+}
+
+/// Splits a version into its numeric components, `17.0.10` into `[17, 0, 10]`.
+fn numeric_components(version: &str) -> Vec<u64> {
+    version.split(|e: char| !e.is_ascii_digit())
+        .filter_map(|e| e.parse().ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compares_versions_before_builds() {
+        assert_eq!(compare_versions("17.0.10+7", "17+35"), Ordering::Greater);
+        assert_eq!(compare_versions("17.0.10+7", "17.0.9+9"), Ordering::Greater);
+        assert_eq!(compare_versions("21-ea+5", "21.0.1+12"), Ordering::Less);
+    }
+
+    #[test]
+    fn pads_missing_components() {
+        assert_eq!(compare_versions("17", "17.0.0"), Ordering::Equal);
+        assert_eq!(compare_versions("17+35", "17.0.0+36"), Ordering::Less);
+    }
+
+    #[test]
+    fn breaks_ties_by_build() {
+        assert_eq!(compare_versions("1.8.0_402-b06", "1.8.0_392-b08"), Ordering::Greater);
+        assert_eq!(compare_versions("17.0.10+7", "17.0.10+13"), Ordering::Less);
+        assert_eq!(compare_versions("17.0.10+7-LTS", "17.0.10+7"), Ordering::Equal);
+    }
+}
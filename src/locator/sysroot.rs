@@ -0,0 +1,305 @@
+use crate::locator::list_dir;
+use std::collections::VecDeque;
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf, Prefix};
+
+/// The metadata key for the path of an install within the sysroot it was found in,
+/// such as `/usr/lib/jvm/java-17-openjdk-amd64`.
+pub const SYSROOT_PATH: &str = "sysroot.path";
+
+/// The most symlinks followed while resolving a path, matching Linux's `MAXSYMLINKS`.
+const MAX_SYMLINKS: usize = 40;
+
+/// The filesystem locators search, either the host itself or a directory containing
+/// another system, such as an extracted container image or a chroot.
+///
+/// Within a sysroot, absolute paths are re-rooted under the sysroot directory and
+/// symlinks are resolved as if it were `/`. The host's environment variables and
+/// user directories don't apply, instead every user home in the sysroot is searched.
+/// The sysroot is assumed to use the same platform layout as the host.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Sysroot {
+    root: Option<PathBuf>,
+}
+
+/// The well known directories of a user, see the `dirs` crate for their meaning.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserDirs {
+    pub home: PathBuf,
+    pub config: PathBuf,
+    pub data: PathBuf,
+    pub data_local: PathBuf,
+    pub cache: PathBuf,
+    pub state: Option<PathBuf>,
+}
+
+impl Sysroot {
+    /// The host system.
+    pub fn host() -> Self {
+        Default::default()
+    }
+
+    /// The system contained in the given directory.
+    ///
+    /// * `root` - The directory to treat as `/`.
+    pub fn new(root: impl AsRef<Path>) -> Self {
+        Self {
+            root: Some(root.as_ref().to_path_buf()),
+        }
+    }
+
+    /// Gets the sysroot directory, None for the host.
+    pub fn root(&self) -> Option<&Path> {
+        self.root.as_deref()
+    }
+
+    pub fn is_host(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Maps an absolute path on the searched system to a path on the host.
+    ///
+    /// * `path` - The path, such as `/usr/lib/jvm`.
+    pub fn path(&self, path: impl AsRef<Path>) -> PathBuf {
+        let path = path.as_ref();
+        let Some(root) = &self.root else {
+            return path.to_path_buf();
+        };
+        let relative: PathBuf = path.components()
+            .filter(|e| !matches!(e, Component::Prefix(_) | Component::RootDir))
+            .collect();
+        root.join(relative)
+    }
+
+    /// Maps a path on the host back to its absolute path on the searched system.
+    ///
+    /// # Returns
+    /// Some containing the path, otherwise None if it is outside the sysroot.
+    pub fn target_path(&self, path: impl AsRef<Path>) -> Option<PathBuf> {
+        let Some(root) = &self.root else {
+            return Some(path.as_ref().to_path_buf());
+        };
+        Some(Path::new("/").join(path.as_ref().strip_prefix(root).ok()?))
+    }
+
+    /// Gets a non-empty environment variable of the searched system.
+    ///
+    /// Environment variables are never available within a sysroot.
+    pub fn var_os(&self, name: &str) -> Option<OsString> {
+        if self.root.is_some() {
+            return None;
+        }
+        env::var_os(name).filter(|e| !e.is_empty())
+    }
+
+    /// Gets the directories of each user of the searched system.
+    ///
+    /// On the host, this is only the current user. Within a sysroot this is every
+    /// directory in `/home`, or `/Users` on macOS and Windows, along with `/root`.
+    pub fn users(&self) -> Vec<UserDirs> {
+        if self.root.is_none() {
+            let Some(home) = dirs::home_dir() else {
+                return Vec::new();
+            };
+            let defaults = UserDirs::from_home(home);
+            return vec![UserDirs {
+                config: dirs::config_dir().unwrap_or(defaults.config),
+                data: dirs::data_dir().unwrap_or(defaults.data),
+                data_local: dirs::data_local_dir().unwrap_or(defaults.data_local),
+                cache: dirs::cache_dir().unwrap_or(defaults.cache),
+                state: dirs::state_dir().or(defaults.state),
+                home: defaults.home,
+            }];
+        }
+
+        let homes = if cfg!(any(target_os = "macos", target_os = "windows")) { "/Users" } else { "/home" };
+        let mut users: Vec<UserDirs> = list_dir(self, self.path(homes)).into_iter()
+            .map(|e| e.path())
+            .filter(|e| self.is_dir(e))
+            .map(UserDirs::from_home)
+            .collect();
+        let root = self.path("/root");
+        if self.is_dir(&root) {
+            users.push(UserDirs::from_home(root));
+        }
+        users
+    }
+
+    /// Gets the directories of a tool, from an environment variable of the searched
+    /// system, otherwise the given directory within each user's home.
+    ///
+    /// * `var` - The environment variable, such as `SDKMAN_DIR`.
+    /// * `relative` - The directory relative to each home, such as `.sdkman`.
+    pub fn dirs_from_env_or_home(&self, var: &str, relative: impl AsRef<Path>) -> Vec<PathBuf> {
+        match self.var_os(var) {
+            Some(dir) => vec![PathBuf::from(dir)],
+            None => self.users().into_iter().map(|e| e.home.join(relative.as_ref())).collect(),
+        }
+    }
+
+    /// Resolves all symlinks in a host path, like [`fs::canonicalize`].
+    ///
+    /// Within a sysroot, absolute symlinks are resolved relative to the sysroot, and
+    /// `..` never leaves it. On Windows, the `\\?\` prefix is stripped from the result.
+    pub fn canonicalize(&self, path: impl AsRef<Path>) -> io::Result<PathBuf> {
+        let Some(root) = &self.root else {
+            return fs::canonicalize(path).map(strip_verbatim);
+        };
+        let not_found = || io::Error::new(io::ErrorKind::NotFound, "path is outside the sysroot");
+        let relative = path.as_ref().strip_prefix(root).map_err(|_| not_found())?;
+
+        let mut pending: VecDeque<OsString> = relative.components().map(|e| e.as_os_str().to_os_string()).collect();
+        let mut resolved = PathBuf::new();
+        let mut links = 0;
+        while let Some(component) = pending.pop_front() {
+            match Path::new(&component).components().next() {
+                Some(Component::Normal(name)) => resolved.push(name),
+                Some(Component::ParentDir) => {
+                    resolved.pop();
+                    continue;
+                }
+                _ => continue,
+            }
+
+            let host = root.join(&resolved);
+            if !fs::symlink_metadata(&host)?.file_type().is_symlink() {
+                continue;
+            }
+            links += 1;
+            if links > MAX_SYMLINKS {
+                return Err(io::Error::other("too many levels of symbolic links"));
+            }
+
+            let target = fs::read_link(&host)?;
+            resolved.pop();
+            if target.has_root() {
+                resolved = PathBuf::new();
+            }
+            for component in target.components().rev() {
+                pending.push_front(component.as_os_str().to_os_string());
+            }
+        }
+        Ok(root.join(resolved))
+    }
+
+    /// Reads a host file, resolving symlinks with [`Sysroot::canonicalize`].
+    pub fn read_to_string(&self, path: impl AsRef<Path>) -> io::Result<String> {
+        fs::read_to_string(self.canonicalize(path)?)
+    }
+
+    /// Checks if a host path exists, resolving symlinks with [`Sysroot::canonicalize`].
+    pub fn exists(&self, path: impl AsRef<Path>) -> bool {
+        self.canonicalize(path).is_ok()
+    }
+
+    /// Checks if a host path is a file, resolving symlinks with [`Sysroot::canonicalize`].
+    pub fn is_file(&self, path: impl AsRef<Path>) -> bool {
+        self.canonicalize(path).is_ok_and(|e| e.is_file())
+    }
+
+    /// Checks if a host path is a directory, resolving symlinks with [`Sysroot::canonicalize`].
+    pub fn is_dir(&self, path: impl AsRef<Path>) -> bool {
+        self.canonicalize(path).is_ok_and(|e| e.is_dir())
+    }
+}
+
+/// Strips the verbatim prefix [`fs::canonicalize`] adds on Windows, `\\?\C:\` to `C:\`,
+/// so canonical paths compare equal to those from the environment or the registry.
+fn strip_verbatim(path: PathBuf) -> PathBuf {
+    let mut components = path.components();
+    let Some(Component::Prefix(prefix)) = components.next() else {
+        return path;
+    };
+    let stripped = match prefix.kind() {
+        Prefix::VerbatimDisk(disk) => format!("{}:", disk as char),
+        Prefix::VerbatimUNC(server, share) => format!("\\\\{}\\{}", server.to_string_lossy(), share.to_string_lossy()),
+        _ => return path,
+    };
+    PathBuf::from(stripped).join(components.as_path())
+}
+
+impl UserDirs {
+    /// Derives the directories of a user from their home, using the platform's defaults.
+    fn from_home(home: PathBuf) -> Self {
+        if cfg!(target_os = "windows") {
+            Self {
+                config: home.join("AppData/Roaming"),
+                data: home.join("AppData/Roaming"),
+                data_local: home.join("AppData/Local"),
+                cache: home.join("AppData/Local"),
+                state: None,
+                home,
+            }
+        } else if cfg!(target_os = "macos") {
+            Self {
+                config: home.join("Library/Application Support"),
+                data: home.join("Library/Application Support"),
+                data_local: home.join("Library/Application Support"),
+                cache: home.join("Library/Caches"),
+                state: None,
+                home,
+            }
+        } else {
+            Self {
+                config: home.join(".config"),
+                data: home.join(".local/share"),
+                data_local: home.join(".local/share"),
+                cache: home.join(".cache"),
+                state: Some(home.join(".local/state")),
+                home,
+            }
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::symlink;
+    use tempfile::TempDir;
+
+    fn sysroot() -> (TempDir, PathBuf) {
+        let temp = TempDir::new().unwrap();
+        let root = fs::canonicalize(temp.path()).unwrap();
+        fs::create_dir_all(root.join("usr/lib/jvm/java-17")).unwrap();
+        (temp, root)
+    }
+
+    #[test]
+    fn resolves_absolute_links_within_root() {
+        let (_temp, root) = sysroot();
+        symlink("/usr/lib/jvm/java-17", root.join("usr/lib/jvm/default-java")).unwrap();
+
+        let sysroot = Sysroot::new(&root);
+        assert_eq!(sysroot.canonicalize(root.join("usr/lib/jvm/default-java")).unwrap(), root.join("usr/lib/jvm/java-17"));
+        assert!(sysroot.is_dir(root.join("usr/lib/jvm/default-java")));
+    }
+
+    #[test]
+    fn never_escapes_root() {
+        let (_temp, root) = sysroot();
+        // Both exist on the host, but not within the sysroot.
+        symlink("/tmp", root.join("usr/lib/jvm/absolute")).unwrap();
+        symlink("../../../..", root.join("usr/lib/jvm/relative")).unwrap();
+
+        let sysroot = Sysroot::new(&root);
+        assert!(!sysroot.exists(root.join("usr/lib/jvm/absolute")));
+        assert_eq!(sysroot.canonicalize(root.join("usr/lib/jvm/relative")).unwrap(), root);
+        assert!(sysroot.canonicalize(root.join("usr/lib/jvm/relative/tmp")).is_err());
+        assert!(sysroot.canonicalize(Path::new("/usr/lib/jvm")).is_err());
+    }
+
+    #[test]
+    fn fails_on_link_loops() {
+        let (_temp, root) = sysroot();
+        symlink("/usr/lib/jvm/b", root.join("usr/lib/jvm/a")).unwrap();
+        symlink("a", root.join("usr/lib/jvm/b")).unwrap();
+
+        let sysroot = Sysroot::new(&root);
+        assert!(sysroot.canonicalize(root.join("usr/lib/jvm/a")).is_err());
+        assert!(!sysroot.exists(root.join("usr/lib/jvm/b/java-17")));
+    }
+}
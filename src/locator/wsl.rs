@@ -0,0 +1,146 @@
+use crate::locator::platform::WINDOWS_PATHS;
+use crate::locator::sysroot::Sysroot;
+use crate::locator::{add_candidate, list_dir, JavaCandidate, JavaLocator};
+use std::env;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// The metadata key for the Windows path of an install, such as `C:\Program Files\Java\jdk-17`.
+pub const WSL_WINDOWS_PATH: &str = "wsl.windows_path";
+
+/// Registered by WSL to run Windows executables, only present within WSL.
+const WSL_INTEROP: &str = "/proc/sys/fs/binfmt_misc/WSLInterop";
+
+/// The WSL configuration file, which may move the drive mounts.
+const WSL_CONF: &str = "/etc/wsl.conf";
+
+/// The default directory Windows drives are mounted in.
+const DEFAULT_MOUNT_ROOT: &str = "/mnt/";
+
+/// Profiles in `C:\Users` which don't belong to a real user.
+const SYSTEM_PROFILES: &[&str] = &["All Users", "Default", "Default User", "Public"];
+
+/// A JavaLocator capable of locating JVM's installed on the Windows host, from
+/// within the Windows Subsystem for Linux.
+///
+/// The common Windows install locations, along with each user's `.jdks`, are
+/// searched through the drive mounts. Only homes with a `bin/java.exe` are found.
+/// Windows executables aren't run, their properties are read from the `release` file.
+///
+/// Nothing is found outside WSL, or within a sysroot.
+#[derive(Default)]
+pub struct WslJavaLocator {
+    mount_root: Option<PathBuf>,
+}
+
+impl WslJavaLocator {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Create a locator which finds Windows drives in the given directory, instead
+    /// of the directory configured in `/etc/wsl.conf`.
+    ///
+    /// * `mount_root` - The directory containing the drive mounts, such as `/mnt`.
+    pub fn with_mount_root(mount_root: impl AsRef<Path>) -> Self {
+        Self {
+            mount_root: Some(mount_root.as_ref().to_path_buf()),
+        }
+    }
+
+    /// Checks if we are running within WSL.
+    pub fn is_wsl() -> bool {
+        if Path::new(WSL_INTEROP).exists() || env::var_os("WSL_DISTRO_NAME").is_some_and(|e| !e.is_empty()) {
+            return true;
+        }
+        // WSL kernels identify themselves, `5.15.146.1-microsoft-standard-WSL2`.
+        fs::read_to_string("/proc/sys/kernel/osrelease")
+            .is_ok_and(|e| e.to_lowercase().contains("microsoft"))
+    }
+
+    /// Gets the directory Windows drives are mounted in, from the `[automount]`
+    /// section of `/etc/wsl.conf`.
+    fn mount_root(&self) -> PathBuf {
+        if let Some(mount_root) = &self.mount_root {
+            return mount_root.clone();
+        }
+
+        let contents = fs::read_to_string(WSL_CONF).unwrap_or_default();
+        let mut section = "";
+        for line in contents.lines().map(str::trim) {
+            if let Some(name) = line.strip_prefix('[').and_then(|e| e.strip_suffix(']')) {
+                section = name.trim();
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = value.trim().trim_matches('"');
+            if section.eq_ignore_ascii_case("automount") && key.trim() == "root" && !value.is_empty() {
+                return PathBuf::from(value);
+            }
+        }
+        PathBuf::from(DEFAULT_MOUNT_ROOT)
+    }
+}
+
+impl JavaLocator for WslJavaLocator {
+    fn name(&self) -> &'static str {
+        "WSL"
+    }
+
+    fn find_candidates(&self, sysroot: &Sysroot) -> Option<Vec<JavaCandidate>> {
+        if !sysroot.is_host() || !Self::is_wsl() {
+            return None;
+        }
+
+        let mount_root = self.mount_root();
+        let mut vec: Vec<JavaCandidate> = Vec::new();
+        log_debug!("Searching for JVM's installed on the Windows host through: {:?}", &mount_root);
+        for path in WINDOWS_PATHS {
+            scan_windows_folder(&mut vec, sysroot, &mount_root, &to_wsl_path(&mount_root, path));
+        }
+
+        for user in list_dir(sysroot, to_wsl_path(&mount_root, "C:/Users")) {
+            let name = user.file_name();
+            if SYSTEM_PROFILES.iter().any(|e| name.eq(*e)) {
+                continue;
+            }
+            scan_windows_folder(&mut vec, sysroot, &mount_root, &user.path().join(".jdks"));
+        }
+        Some(vec)
+    }
+}
+
+/// Adds each Windows home in the given directory. The Windows binaries can't be run,
+/// so homes without a `release` file are rejected.
+fn scan_windows_folder(vec: &mut Vec<JavaCandidate>, sysroot: &Sysroot, mount_root: &Path, dir: &Path) {
+    log_debug!("Scanning Windows folder for JVM's: {:?}", dir);
+    for entry in list_dir(sysroot, dir) {
+        let home = entry.path();
+        let Some(candidate) = add_candidate(vec, sysroot, &home) else {
+            continue;
+        };
+        if let Some(windows_path) = to_windows_path(mount_root, &home) {
+            candidate.with_metadata(WSL_WINDOWS_PATH, windows_path);
+        }
+    }
+}
+
+/// Maps a Windows path to its location within WSL, `C:/Program Files` to `/mnt/c/Program Files`.
+fn to_wsl_path(mount_root: &Path, windows_path: &str) -> PathBuf {
+    let (drive, rest) = windows_path.split_once(':').unwrap_or(("c", windows_path));
+    mount_root.join(drive.to_lowercase()).join(rest.trim_start_matches(['/', '\\']))
+}
+
+/// Maps a path within the drive mounts back to its Windows path, `/mnt/c/Program Files`
+/// to `C:\Program Files`.
+fn to_windows_path(mount_root: &Path, path: &Path) -> Option<String> {
+    let mut components = path.strip_prefix(mount_root).ok()?.components().filter_map(|e| match e {
+        Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+        _ => None,
+    });
+    let drive = components.next()?.to_uppercase();
+    let rest: Vec<String> = components.collect();
+    Some(format!("{}:\\{}", drive, rest.join("\\")))
+}
@@ -39,8 +39,11 @@ impl Execute for Commands {
 }
 
 fn main() -> io::Result<()> {
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
     env_logger::Builder::from_env(Env::default().default_filter_or(format!("{},rustls=off,ureq=off,ureq_proto=off", if cli.verbose { "debug" } else { "info" }))).init();
 
+    if let Commands::List(list) = &mut cli.command {
+        list.verbose = cli.verbose;
+    }
     cli.command.execute()
 }